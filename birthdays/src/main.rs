@@ -1,16 +1,23 @@
 use anyhow::Result;
-use chrono::{Datelike, NaiveDate, Utc};
+use chrono::{Datelike, NaiveDate};
 use grep::{
     regex::RegexMatcher,
     searcher::{BinaryDetection, Searcher, SearcherBuilder, Sink, SinkError, SinkMatch},
 };
+use std::path::PathBuf;
 use utils::{
+    clock::{Clock, SystemClock},
     content::CodeBlock,
     events::{Event, SerdeEvent},
+    locale::{Locale, format_age},
     page::Page,
 };
 use walkdir::WalkDir;
 
+mod options;
+
+use options::{Feb29, Options};
+
 #[derive(Default)]
 struct Detector {
     detected: bool,
@@ -41,57 +48,92 @@ impl Sink for Detector {
     }
 }
 
-fn main() -> Result<()> {
-    let pattern = "^birthday: \\d{4}-\\d{2}-\\d{2}";
-    let matcher = RegexMatcher::new_line_matcher(pattern)?;
+/// Date of the birthday during the year, February 29th being moved according to `feb29` on years
+/// without one
+fn birthday_in(birthday: NaiveDate, year: i32, feb29: Feb29) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, birthday.month(), birthday.day()).unwrap_or_else(|| {
+        let date = match feb29 {
+            Feb29::Mar1 => NaiveDate::from_ymd_opt(year, 3, 1),
+            Feb29::Feb28 => NaiveDate::from_ymd_opt(year, 2, 28),
+        }
+        .unwrap();
+        eprintln!(
+            "No February 29th in {year}, birthday of {birthday} on {date} (--feb29 {})",
+            feb29.name()
+        );
+        date
+    })
+}
+
+/// Events wishing the birthdays of the notes under `options.path` during the year of `today`
+///
+/// The notes are searched by `options.parallel` threads, the events being sorted by path so the
+/// output does not depend on the scheduling of the threads
+fn birthdays(options: &Options, today: NaiveDate) -> Result<Vec<CodeBlock>> {
+    let mut paths = vec![];
+    for result in WalkDir::new(&options.path) {
+        match result {
+            Ok(dent) if dent.file_type().is_file() => paths.push(dent.into_path()),
+            Ok(_) => {}
+            Err(err) => eprintln!("{err}"),
+        }
+    }
+
+    let chunk_size = paths.len().div_ceil(options.parallel.get()).max(1);
+    let mut blocks = std::thread::scope(|scope| {
+        let handles = paths
+            .chunks(chunk_size)
+            .map(|paths| scope.spawn(move || search(options, today, paths)))
+            .collect::<Vec<_>>();
+
+        handles.into_iter().try_fold(vec![], |mut blocks, handle| {
+            blocks.extend(handle.join().expect("search thread panicked")?);
+            Ok::<_, anyhow::Error>(blocks)
+        })
+    })?;
+
+    blocks.sort_by_key(|(path, _)| *path);
+    Ok(blocks.into_iter().map(|(_, block)| block).collect())
+}
+
+/// Events wishing the birthdays of the notes at the given paths, along with their path
+fn search<'a>(
+    options: &Options,
+    today: NaiveDate,
+    paths: &'a [PathBuf],
+) -> Result<Vec<(&'a PathBuf, CodeBlock)>> {
+    let root = options.path.as_path();
+    let pattern = format!("^{}: \\d{{4}}-\\d{{2}}-\\d{{2}}", options.property);
+    let matcher = RegexMatcher::new_line_matcher(&pattern)?;
     let mut searcher = SearcherBuilder::new()
         .binary_detection(BinaryDetection::quit(b'\x00'))
         .line_number(false)
         .build();
 
-    let options = match utils::options::parse(std::env::args_os()) {
-        Ok(options) => options,
-        Err(err) => err.exit(),
-    };
-
-    let today = Utc::now().date_naive();
-    std::env::set_current_dir(options.path)?;
+    let mut blocks = vec![];
 
-    for result in WalkDir::new(".") {
-        let dent = match result {
-            Ok(dent) => dent,
-            Err(err) => {
-                eprintln!("{err}");
-                continue;
-            }
-        };
-        if !dent.file_type().is_file() {
-            continue;
-        }
+    for file in paths {
         let mut detector = Detector::default();
-        searcher.search_path(&matcher, dent.path(), &mut detector)?;
+        searcher.search_path(&matcher, file, &mut detector)?;
 
         if detector.detected() {
-            let page = Page::try_from(dent.path())?;
+            let page = Page::try_from(file.as_path())?;
             if let Some(birthday) = page
-                .get_property("birthday")
+                .get_property(&options.property)
                 .and_then(|bd| bd.as_str())
                 .and_then(|bd| bd.parse::<NaiveDate>().ok())
             {
-                let date = NaiveDate::from_ymd_opt(today.year(), birthday.month(), birthday.day())
-                    .unwrap_or_else(|| {
-                        NaiveDate::from_yo_opt(today.year(), birthday.ordinal()).unwrap()
-                    });
-                let name = page
-                    .get_property("aliases")
-                    .and_then(|aliases| aliases.as_sequence_get(0))
+                let date = birthday_in(birthday, today.year(), options.feb29);
+                let name = options
+                    .alias
+                    .and_then(|index| page.get_property("aliases")?.as_sequence_get(index))
                     .map_or_else(
-                        || dent.path().file_stem().unwrap().to_str(),
+                        || file.file_stem().unwrap().to_str(),
                         |alias| alias.as_str(),
                     )
                     .unwrap();
 
-                let path = dent.path().strip_prefix("./")?;
+                let path = file.strip_prefix(root)?;
                 let ext = path
                     .extension()
                     .unwrap()
@@ -106,15 +148,187 @@ fn main() -> Result<()> {
                 let content = date.years_since(birthday).map_or_else(
                     || format!("- [ ] Wish [[{page}|{name}]] a happy birthday"),
                     |years| {
-                        format!("- [ ] [[{page}|{name}]] is {years} years old, wish them a happy birthday!")
+                        let age = format_age(years, Locale::English);
+                        format!("- [ ] [[{page}|{name}]] is {age}, wish them a happy birthday!")
                     },
                 );
-                let event = Event::date(date, content);
+                let event = Event::date(date, content).with_icon("🎂");
                 let block = CodeBlock::toml(toml::to_string(&SerdeEvent::from(event))?);
-
-                println!("{block}");
+                blocks.push((file, block));
             }
         }
     }
+
+    Ok(blocks)
+}
+
+/// Write the events as the only content of the page at `path`, replacing the existing one
+fn write_events(path: PathBuf, blocks: Vec<CodeBlock>) -> Result<()> {
+    let mut page = Page::empty(path);
+    for block in blocks {
+        page.append_entry(block);
+        page.append_entry(String::new());
+    }
+    page.write()?;
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    let options = match options::parse(std::env::args_os()) {
+        Ok(options) => options,
+        Err(err) => err.exit(),
+    };
+
+    let today = SystemClock.today();
+    let blocks = birthdays(&options, today)?;
+
+    if let Some(output) = options.output {
+        write_events(options.path.join(output), blocks)?;
+    } else {
+        for block in blocks {
+            println!("{block}");
+        }
+    }
+
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_fs::prelude::*;
+    use claim::assert_ok;
+    use indoc::indoc;
+    use utils::content::Entry;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn options(path: &std::path::Path) -> Options {
+        Options {
+            path: path.to_path_buf(),
+            feb29: Feb29::Mar1,
+            output: None,
+            property: String::from(options::DEFAULT_PROPERTY),
+            alias: Some(0),
+            parallel: std::num::NonZeroUsize::MIN,
+        }
+    }
+
+    #[test]
+    fn birthday_in_year() {
+        assert_eq!(
+            date(2025, 7, 14),
+            birthday_in(date(1990, 7, 14), 2025, Feb29::Mar1)
+        );
+
+        let birthday = date(2000, 2, 29);
+        assert_eq!(date(2024, 2, 29), birthday_in(birthday, 2024, Feb29::Mar1));
+        assert_eq!(date(2024, 2, 29), birthday_in(birthday, 2024, Feb29::Feb28));
+        assert_eq!(date(2025, 3, 1), birthday_in(birthday, 2025, Feb29::Mar1));
+        assert_eq!(date(2025, 2, 28), birthday_in(birthday, 2025, Feb29::Feb28));
+    }
+
+    #[test]
+    fn write_events_file() {
+        let temp_dir = assert_ok!(assert_fs::TempDir::new());
+        assert_ok!(temp_dir.child("people/Alice.md").write_str(indoc! {"
+            ---
+            birthday: 1990-07-14
+            aliases:
+              - Alice Liddell
+            ---
+        "}));
+        assert_ok!(temp_dir.child("people/Bob.md").write_str(indoc! {"
+            ---
+            birthday: 2000-02-29
+            ---
+        "}));
+        assert_ok!(
+            temp_dir
+                .child("Notes.md")
+                .write_str("No birthday: 2000-01-01\n")
+        );
+
+        let blocks = assert_ok!(birthdays(&options(temp_dir.path()), date(2025, 1, 1)));
+        assert_eq!(2, blocks.len());
+
+        let file = temp_dir.child("events/birthdays.md");
+        assert_ok!(write_events(file.to_path_buf(), blocks));
+
+        let page = assert_ok!(Page::try_from(file.path()));
+        let events = page
+            .entries()
+            .filter_map(|entry| match entry {
+                Entry::CodeBlock(block) => Some(assert_ok!(Event::try_from(block))),
+                Entry::Line(_) => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(2, events.len());
+        assert!(events.iter().any(|event| event.matches(date(2025, 7, 14))));
+        assert!(events.iter().any(|event| event.matches(date(2025, 3, 1))));
+    }
+
+    #[test]
+    fn property_and_alias() {
+        let temp_dir = assert_ok!(assert_fs::TempDir::new());
+        assert_ok!(temp_dir.child("Alice.md").write_str(indoc! {"
+            ---
+            dob: 1990-07-14
+            aliases:
+              - Alice Liddell
+              - Ally
+            ---
+        "}));
+        assert_ok!(temp_dir.child("Bob.md").write_str(indoc! {"
+            ---
+            birthday: 1985-03-02
+            ---
+        "}));
+        let today = date(2025, 1, 1);
+
+        let mut options = options(temp_dir.path());
+        options.property = String::from("dob");
+        let blocks = assert_ok!(birthdays(&options, today));
+        assert_eq!(1, blocks.len());
+        assert!(blocks[0].to_string().contains("[[Alice|Alice Liddell]]"));
+
+        options.alias = Some(1);
+        let blocks = assert_ok!(birthdays(&options, today));
+        assert!(blocks[0].to_string().contains("[[Alice|Ally]]"));
+
+        // Missing alias
+        options.alias = Some(2);
+        let blocks = assert_ok!(birthdays(&options, today));
+        assert!(blocks[0].to_string().contains("[[Alice|Alice]]"));
+
+        options.alias = None;
+        let blocks = assert_ok!(birthdays(&options, today));
+        assert!(blocks[0].to_string().contains("[[Alice|Alice]]"));
+    }
+
+    #[test]
+    fn parallel_output_is_stable() {
+        let temp_dir = assert_ok!(assert_fs::TempDir::new());
+        for i in 1..=20 {
+            assert_ok!(
+                temp_dir
+                    .child(format!("people/{}/Person {i}.md", i % 3))
+                    .write_str(&format!("---\nbirthday: 1990-01-{i:02}\n---\n"))
+            );
+        }
+        let today = date(2025, 1, 1);
+
+        let mut options = options(temp_dir.path());
+        let serial = assert_ok!(birthdays(&options, today));
+        assert_eq!(20, serial.len());
+
+        for parallel in [2, 3, 8, 32] {
+            options.parallel = assert_ok!(parallel.try_into());
+            for _ in 0..3 {
+                assert_eq!(serial, assert_ok!(birthdays(&options, today)));
+            }
+        }
+    }
+}
@@ -0,0 +1,201 @@
+use clap::ValueEnum;
+use std::ffi::OsString;
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+
+/// Day to wish the birthdays of February 29th on years without one
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, ValueEnum)]
+pub enum Feb29 {
+    /// March 1st
+    #[default]
+    Mar1,
+    /// February 28th
+    Feb28,
+}
+
+impl Feb29 {
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Mar1 => "mar1",
+            Self::Feb28 => "feb28",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Options {
+    pub path: PathBuf,
+    pub feb29: Feb29,
+    pub output: Option<PathBuf>,
+    /// Property holding the date of birth
+    pub property: String,
+    /// Alias used as name, the file name being used if `None` or missing
+    pub alias: Option<usize>,
+    /// Number of threads searching the notes
+    pub parallel: NonZeroUsize,
+}
+
+/// Property holding the date of birth, unless configured otherwise
+pub const DEFAULT_PROPERTY: &str = "birthday";
+
+/// Property names are matched verbatim in the notes, so only accept plain names
+fn property_name(name: &str) -> Result<String, String> {
+    if !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+    {
+        Ok(name.to_owned())
+    } else {
+        Err(String::from(
+            "expected letters, digits, underscores and dashes only",
+        ))
+    }
+}
+
+/// Parse given arguments
+///
+/// # Errors
+/// `clap::error::Error`: Error parsing arguments
+pub fn parse<I, T>(args_iter: I) -> Result<Options, clap::error::Error>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<OsString> + Clone,
+{
+    use clap::{arg, command, value_parser};
+
+    let matches = command!()
+        .arg(
+            arg!(path: -p --path <PATH> "Path to notes")
+                .required(true)
+                .value_parser(value_parser!(PathBuf)),
+        )
+        .arg(
+            arg!(feb29: --feb29 <DAY> "Day to wish the birthdays of February 29th on years without one")
+                .required(false)
+                .default_value(Feb29::default().name())
+                .value_parser(value_parser!(Feb29)),
+        )
+        .arg(
+            arg!(output: -o --output <FILE> "Write the events to this page of the notes instead of printing them")
+                .long_help("Write the events to this page of the notes instead of printing them\n\nThe page is created or overwritten, e.g. events/birthdays.md")
+                .required(false)
+                .value_parser(value_parser!(PathBuf)),
+        )
+        .arg(
+            arg!(property: --property <NAME> "Property holding the date of birth")
+                .required(false)
+                .default_value(DEFAULT_PROPERTY)
+                .value_parser(property_name),
+        )
+        .arg(
+            arg!(alias: --alias <INDEX> "Index of the alias used as name, the file name being used if there is none")
+                .required(false)
+                .default_value("0")
+                .value_parser(value_parser!(usize)),
+        )
+        .arg(
+            arg!(no_alias: --"no-alias" "Always use the file name as name")
+                .conflicts_with("alias"),
+        )
+        .arg(
+            arg!(parallel: -j --parallel <N> "Number of threads searching the notes")
+                .required(false)
+                .default_value("1")
+                .value_parser(value_parser!(NonZeroUsize)),
+        )
+        .try_get_matches_from(args_iter)?;
+
+    Ok(Options {
+        path: matches
+            .get_one::<PathBuf>("path")
+            .unwrap_or_else(|| {
+                unreachable!("'PATH' is required and parsing will fail if its missing")
+            })
+            .clone(),
+        feb29: matches
+            .get_one::<Feb29>("feb29")
+            .copied()
+            .unwrap_or_default(),
+        output: matches.get_one::<PathBuf>("output").cloned(),
+        property: matches
+            .get_one::<String>("property")
+            .cloned()
+            .unwrap_or_else(|| DEFAULT_PROPERTY.to_owned()),
+        alias: if matches.get_flag("no_alias") {
+            None
+        } else {
+            matches.get_one::<usize>("alias").copied()
+        },
+        parallel: matches
+            .get_one::<NonZeroUsize>("parallel")
+            .copied()
+            .unwrap_or(NonZeroUsize::MIN),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn feb29() {
+        let options = parse(["birthdays", "-p", "notes"]).unwrap();
+        assert_eq!(PathBuf::from("notes"), options.path);
+        assert_eq!(Feb29::Mar1, options.feb29);
+
+        let options = parse(["birthdays", "-p", "notes", "--feb29", "feb28"]).unwrap();
+        assert_eq!(Feb29::Feb28, options.feb29);
+
+        assert!(parse(["birthdays", "-p", "notes", "--feb29", "mar2"]).is_err());
+        assert!(parse(["birthdays"]).is_err());
+    }
+
+    #[test]
+    fn output() {
+        let options = parse(["birthdays", "-p", "notes"]).unwrap();
+        assert_eq!(None, options.output);
+
+        let options = parse(["birthdays", "-p", "notes", "-o", "events/birthdays.md"]).unwrap();
+        assert_eq!(Some(PathBuf::from("events/birthdays.md")), options.output);
+    }
+
+    #[test]
+    fn property_and_alias() {
+        let options = parse(["birthdays", "-p", "notes"]).unwrap();
+        assert_eq!(DEFAULT_PROPERTY, options.property);
+        assert_eq!(Some(0), options.alias);
+
+        let options = parse([
+            "birthdays",
+            "-p",
+            "notes",
+            "--property",
+            "dob",
+            "--alias",
+            "1",
+        ])
+        .unwrap();
+        assert_eq!("dob", options.property);
+        assert_eq!(Some(1), options.alias);
+
+        let options = parse(["birthdays", "-p", "notes", "--no-alias"]).unwrap();
+        assert_eq!(None, options.alias);
+
+        assert!(parse(["birthdays", "-p", "notes", "--property", "date.of.birth"]).is_err());
+        assert!(parse(["birthdays", "-p", "notes", "--property", ""]).is_err());
+        assert!(parse(["birthdays", "-p", "notes", "--alias", "1", "--no-alias"]).is_err());
+    }
+
+    #[test]
+    fn parallel() {
+        let options = parse(["birthdays", "-p", "notes"]).unwrap();
+        assert_eq!(1, options.parallel.get());
+
+        let options = parse(["birthdays", "-p", "notes", "--parallel", "4"]).unwrap();
+        assert_eq!(4, options.parallel.get());
+
+        assert!(parse(["birthdays", "-p", "notes", "-j", "0"]).is_err());
+    }
+}
@@ -1,6 +1,9 @@
 use anyhow::Result;
+use indicatif::MultiProgress;
 
 mod preparer;
+mod progress;
+mod stats;
 mod utils;
 mod vault;
 
@@ -20,19 +23,76 @@ fn main() -> Result<()> {
         from,
         to,
         path,
+        init,
+        clean,
+        stats,
+        force,
+        skip_existing,
+        since_modified,
+        merge_config,
+        exclude_weekends,
+        no_properties,
+        append_only,
+        strict,
+        preview,
+        post_hook,
+        page_templates,
+        output_dir,
         log_level_filter,
+        log_format,
         page_options,
     } = parse();
 
-    setup_log(log_level_filter)?;
+    let progress = MultiProgress::new();
+    setup_log(log_level_filter, log_format, &progress)?;
 
-    Vault::new(path)?.prepare(from, to, page_options)?;
+    if init {
+        return vault::Config::init(&path);
+    }
+
+    let vault = Vault::new(path)?
+        .with_skip_existing(skip_existing)
+        .with_merge_config(merge_config)
+        .with_exclude_weekends(exclude_weekends)
+        .with_no_properties(no_properties)
+        .with_append_only(append_only)
+        .with_since_modified(since_modified)?
+        .with_post_hook(post_hook)
+        .with_page_templates(page_templates)
+        .with_strict(strict)?
+        .with_output_dir(output_dir)
+        .with_clean(clean, force)
+        .with_preview(preview.is_some())
+        .with_progress(
+            progress::enabled(
+                std::io::IsTerminal::is_terminal(&std::io::stderr()),
+                systemd_journal_logger::connected_to_journal(),
+                log_level_filter,
+            )
+            .then_some(progress),
+        );
+    if stats {
+        print!("{}", stats::Stats::new(vault.events(), from, to));
+        return Ok(());
+    }
+    if let Some(date) = preview {
+        let mut page_options = page_options;
+        page_options.retain(&[options::Period::Day]);
+        return vault.prepare(date, date, page_options);
+    }
+    vault.prepare(from, to, page_options)?;
+    vault.run_post_hook()?;
 
     Ok(())
 }
 
-fn setup_log(level: log::LevelFilter) -> Result<()> {
+fn setup_log(
+    level: log::LevelFilter,
+    format: options::LogFormat,
+    progress: &MultiProgress,
+) -> Result<()> {
     use env_logger::{Builder, Env};
+    use indicatif_log_bridge::LogWrapper;
     use systemd_journal_logger::{connected_to_journal, JournalLog};
 
     // If the output streams of this process are directly connected to the
@@ -51,10 +111,24 @@ fn setup_log(level: log::LevelFilter) -> Result<()> {
             .filter(format!("{name}_LOG"))
             .write_style(format!("{name}_LOG_STYLE"));
 
-        Builder::new()
-            .filter_level(log::LevelFilter::Trace)
-            .parse_env(env)
-            .try_init()?;
+        let mut builder = Builder::new();
+        builder.filter_level(log::LevelFilter::Trace).parse_env(env);
+
+        if format == options::LogFormat::Json {
+            builder.format(|buf, record| {
+                use std::io::Write;
+
+                let line = serde_json::json!({
+                    "level": record.level().as_str(),
+                    "target": record.target(),
+                    "message": record.args().to_string(),
+                });
+                writeln!(buf, "{line}")
+            });
+        }
+
+        // Printed through the progress bar, so the logs and the bar do not overwrite each other
+        LogWrapper::new(progress.clone(), builder.build()).try_init()?;
     }
 
     log::set_max_level(level);
@@ -1,8 +1,14 @@
 use super::Vault;
-use crate::utils::{ToEmbedded, ToLink};
-use anyhow::Result;
-use chrono::{Datelike, Days, IsoWeek, NaiveDate, Weekday};
-use utils::date::{Month, Navigation, ToDateIterator, Year};
+use crate::progress::Progress;
+use crate::utils::{date_range, Link, NoteLink, ToEmbedded, ToLink};
+use anyhow::{anyhow, Result};
+use chrono::{Datelike, Days, NaiveDate};
+use std::fmt::Write;
+use utils::date::{Month, Navigation, ToDateIterator, Week, Year};
+use utils::events::Event;
+use utils::locale::{events_label, long_date, relative_days, weekday_abbrev, weekday_name, Locale};
+use utils::options::day::NavTitle;
+use utils::options::month::DayLabel;
 use utils::options::{GenericPage, GenericSettings, PageOptions};
 
 pub trait Prepare {
@@ -11,7 +17,11 @@ pub trait Prepare {
 
 impl Prepare for Vault {
     fn prepare(&self, from: NaiveDate, to: NaiveDate, mut page_options: PageOptions) -> Result<()> {
-        page_options.update(self.config().settings());
+        if self.merge_config() {
+            page_options.merge(self.config().settings());
+        } else {
+            page_options.update(self.config().settings());
+        }
 
         Preparer {
             from,
@@ -30,18 +40,55 @@ pub struct Preparer<'a> {
     pub vault: &'a Vault,
 }
 
-fn weekday(date: NaiveDate) -> &'static str {
-    match date.weekday() {
-        Weekday::Mon => "Monday",
-        Weekday::Tue => "Tuesday",
-        Weekday::Wed => "Wednesday",
-        Weekday::Thu => "Thursday",
-        Weekday::Fri => "Friday",
-        Weekday::Sat => "Saturday",
-        Weekday::Sun => "Sunday",
+fn weekday(date: NaiveDate, locale: Locale) -> &'static str {
+    weekday_name(date.weekday(), locale)
+}
+
+/// Label of the day in the month page, followed by a space, or nothing
+fn day_label(label: DayLabel, date: NaiveDate, locale: Locale) -> String {
+    match label {
+        DayLabel::Full => format!("{} ", weekday(date, locale)),
+        DayLabel::Abbrev => format!("{} ", weekday_abbrev(date.weekday(), locale)),
+        DayLabel::None => String::new(),
     }
 }
 
+/// Lines of the event for the date, followed by the note it embeds, if any, or a link to it when
+/// not `embedded`
+fn event_lines(event: &Event, date: NaiveDate, vault: &Vault, embedded: bool) -> Vec<String> {
+    let mut lines = event.lines(date).collect::<Vec<_>>();
+    if let Some(note) = event.embed() {
+        let link = NoteLink(note.to_owned());
+        lines.push(if embedded {
+            link.into_embedded(vault).to_string()
+        } else {
+            link.to_string()
+        });
+    }
+    lines
+}
+
+/// Line before the events of the day pages, replaced along with them on the next runs
+const EVENTS_START_MARKER: &str = "<!-- prep:start id=events -->";
+/// Line before the days or weeks of the month pages listing only the days of the prepared range,
+/// so the days of the next runs are listed in order along with them
+const MONTH_START_MARKER: &str = "<!-- prep:start id=month -->";
+/// Line after the generated lines between markers
+const END_MARKER: &str = "<!-- prep:end -->";
+
+/// Wrap the lines in a collapsible callout, e.g. `> [!note]- Events`
+fn callout(kind: &str, title: &str, lines: Vec<String>) -> Vec<String> {
+    std::iter::once(format!("> [!{kind}]- {title}"))
+        .chain(lines.into_iter().map(|line| {
+            if line.is_empty() {
+                ">".to_owned()
+            } else {
+                format!("> {line}")
+            }
+        }))
+        .collect()
+}
+
 impl Preparer<'_> {
     pub fn run(&self) -> Result<()> {
         log::info!(
@@ -55,12 +102,22 @@ impl Preparer<'_> {
         log::debug!("month options: {:?}", self.page_options.month);
         log::debug!("year options: {:?}", self.page_options.year);
 
+        let progress = Progress::new(self.from, self.to, self.vault.progress());
+        if self.page_options.day.settings().is_empty()
+            && self.page_options.week.settings().is_empty()
+        {
+            self.run_months(&progress)?;
+            progress.finish();
+            return Ok(());
+        }
+
         let mut date: NaiveDate = self.from;
         let mut year = Year::from(date.year());
         let mut month = Month::from(date);
-        let mut week = date.iso_week();
+        let mut week = self.week_of(date);
 
         self.day(date)?;
+        progress.advance(date);
         self.week(week)?;
         self.month(month)?;
         self.year(year)?;
@@ -68,8 +125,9 @@ impl Preparer<'_> {
         while date < self.to {
             date = date + Days::new(1);
             self.day(date)?;
+            progress.advance(date);
 
-            let new_week = date.iso_week();
+            let new_week = self.week_of(date);
             if week != new_week {
                 self.week(new_week)?;
                 week = new_week;
@@ -87,9 +145,74 @@ impl Preparer<'_> {
                 month = new_month;
             }
         }
+        progress.finish();
         Ok(())
     }
 
+    /// Prepare the months and years of the range, without going through every day when there are
+    /// no day or week pages to prepare
+    fn run_months(&self, progress: &Progress) -> Result<()> {
+        let last = Month::from(self.to);
+        let mut month = Month::from(self.from);
+        let mut year = month.year();
+        self.year(year)?;
+
+        loop {
+            self.month(month)?;
+            progress.advance(month.last());
+            if month == last {
+                return Ok(());
+            }
+
+            month = month.next();
+            if year != month.year() {
+                year = month.year();
+                self.year(year)?;
+            }
+        }
+    }
+
+    fn week_of(&self, date: NaiveDate) -> Week {
+        Week::new(date, self.vault.config().week_numbering())
+    }
+
+    /// Weeks belonging to the year, i.e. 52 or 53 of them, the first and last ones possibly
+    /// containing days of the previous or next year
+    fn weeks_of(&self, year: Year) -> Vec<Week> {
+        let number = year.first().first().year();
+        let mut week = self.week_of(year.first().first());
+        if week.year() < number {
+            week = week.next();
+        }
+
+        let mut weeks = Vec::with_capacity(year.num_weeks() as usize);
+        while week.year() == number {
+            weeks.push(week);
+            week = week.next();
+        }
+        weeks
+    }
+
+    /// List the events occurring between `from` and `to`, one line per occurrence
+    fn events_lines(&self, from: NaiveDate, to: NaiveDate) -> Vec<String> {
+        let events = self
+            .vault
+            .events()
+            .filter(|ev| ev.matches_range(from, to))
+            .collect::<Vec<_>>();
+
+        from.iter_days()
+            .take_while(|date| *date <= to)
+            .flat_map(|date| {
+                events
+                    .iter()
+                    .filter(move |ev| ev.matches(date))
+                    .flat_map(move |ev| event_lines(ev, date, self.vault, false))
+                    .map(move |line| format!("- {} {line}", date.to_link(self.vault)))
+            })
+            .collect()
+    }
+
     fn year(&self, year: Year) -> Result<()> {
         let settings = self.page_options.year.settings();
         if settings.is_empty() {
@@ -101,8 +224,45 @@ impl Preparer<'_> {
                 page.insert_property("next", year.next().to_link(self.vault));
                 page.insert_property("prev", year.prev().to_link(self.vault));
             }
+            if settings.events {
+                let mut lines = vec![];
+                for month in year.iter() {
+                    let events = self.events_lines(month.first(), month.last());
+                    if !events.is_empty() {
+                        lines.push(format!(
+                            "{} {}",
+                            settings.month_heading_level,
+                            month.to_link(self.vault)
+                        ));
+                        lines.extend(events);
+                    }
+                }
+                page.prepend_lines(lines);
+            }
+            if settings.weeks {
+                page.prepend_lines(
+                    self.weeks_of(year)
+                        .into_iter()
+                        .map(|week| week.to_link(self.vault)),
+                );
+            }
             if settings.month {
-                page.prepend_lines(year.iter().map(|month| month.to_link(self.vault)));
+                if settings.compact {
+                    page.prepend_line(
+                        year.iter()
+                            .map(|month| {
+                                Link {
+                                    title: month.name_abbrev().to_owned(),
+                                    ..month.to_link(self.vault)
+                                }
+                                .to_string()
+                            })
+                            .collect::<Vec<_>>()
+                            .join(" · "),
+                    );
+                } else {
+                    page.prepend_lines(year.iter().map(|month| month.to_link(self.vault)));
+                }
             }
 
             Ok(page)
@@ -120,20 +280,73 @@ impl Preparer<'_> {
                 page.insert_property("next", month.next().to_link(self.vault));
                 page.insert_property("prev", month.prev().to_link(self.vault));
             }
-            if settings.month {
+            if settings.events {
+                let events = self.events_lines(month.first(), month.last());
+                if !events.is_empty() {
+                    let heading = format!(
+                        "{} {}",
+                        settings.week_heading_level,
+                        events_label(self.vault.config().locale())
+                    );
+                    page.prepend_lines(std::iter::once(heading).chain(events));
+                }
+            }
+            // With the days in range, the days listed by the previous runs are kept
+            let listed = |date: &NaiveDate, line: &str| {
+                !settings.days_in_range
+                    || (self.from..=self.to).contains(date)
+                    || page.contains_line(line)
+            };
+            let mut lines = vec![];
+            if settings.weeks {
+                let mut weeks = month
+                    .iter()
+                    .map(|date| self.week_of(date))
+                    .collect::<Vec<_>>();
+                weeks.dedup();
+                for week in weeks {
+                    let line = week
+                        .to_link(self.vault)
+                        .into_embedded(self.vault)
+                        .to_string();
+                    if week
+                        .iter()
+                        .filter(|date| Month::from(*date) == month)
+                        .any(|date| listed(&date, &line))
+                    {
+                        lines.push(line);
+                    }
+                }
+            } else if settings.month {
                 // 31 days max plus 5 weeks headers
-                let mut lines = Vec::with_capacity(36);
-                for (index, date) in month.iter().enumerate() {
-                    if index == 0 || date.weekday() == Weekday::Mon {
-                        lines.push(format!("#### {}", date.iso_week().to_link(self.vault)));
+                lines.reserve(36);
+                let mut previous_week = None;
+                for date in month.iter() {
+                    let day_link = date.to_link(self.vault);
+                    let label = day_label(settings.day_label, date, self.vault.config().locale());
+                    let line = if settings.link_days {
+                        format!("- {label}{day_link}")
+                    } else {
+                        format!("- {label}{}", day_link.into_embedded(self.vault))
+                    };
+                    if self.excluded(date) || !listed(&date, &line) {
+                        continue;
+                    }
+                    let week = self.week_of(date);
+                    if previous_week != Some(week) {
+                        lines.push(format!(
+                            "{} {}",
+                            settings.week_heading_level,
+                            week.to_link(self.vault)
+                        ));
+                        previous_week = Some(week);
                     }
-                    lines.push(format!(
-                        "- {} {}",
-                        weekday(date),
-                        date.to_link(self.vault).into_embedded()
-                    ));
+                    lines.push(line);
                 }
-
+            }
+            if settings.days_in_range {
+                page.replace_marked_lines(MONTH_START_MARKER, END_MARKER, lines);
+            } else {
                 page.prepend_lines(lines);
             }
 
@@ -141,7 +354,14 @@ impl Preparer<'_> {
         })
     }
 
-    fn week(&self, week: IsoWeek) -> Result<()> {
+    /// Whether the day is left out of the day, week and month pages, being a weekend day with
+    /// `exclude_weekends`
+    fn excluded(&self, date: NaiveDate) -> bool {
+        self.vault.exclude_weekends()
+            && self.vault.config().weekend_days().contains(&date.weekday())
+    }
+
+    fn week(&self, week: Week) -> Result<()> {
         let settings = self.page_options.week.settings();
         if settings.is_empty() {
             return Ok(());
@@ -155,14 +375,41 @@ impl Preparer<'_> {
                 page.insert_property("next", week.next().to_link(self.vault));
                 page.insert_property("prev", week.prev().to_link(self.vault));
             }
-            if settings.week {
-                page.prepend_lines(week.iter().map(|date| {
-                    format!(
-                        "- {} {}",
-                        weekday(date),
-                        date.to_link(self.vault).into_embedded()
-                    )
-                }));
+            if settings.date_range {
+                page.insert_property("dates", date_range(&week));
+            }
+            if settings.week || settings.tasks {
+                let mut days = week
+                    .iter()
+                    .filter(|date| !self.excluded(*date))
+                    .collect::<Vec<_>>();
+                if let Some(start) = days
+                    .iter()
+                    .position(|date| Some(date.weekday()) == settings.display_start)
+                {
+                    days.rotate_left(start);
+                }
+
+                let mut lines = vec![];
+                if settings.week {
+                    lines.extend(days.iter().map(|date| {
+                        format!(
+                            "- {} {}",
+                            weekday(*date, self.vault.config().locale()),
+                            date.to_link(self.vault).into_embedded(self.vault)
+                        )
+                    }));
+                }
+                if settings.tasks {
+                    lines.extend(days.iter().map(|date| {
+                        format!(
+                            "- [ ] {} {}",
+                            date.to_link(self.vault),
+                            settings.task_label()
+                        )
+                    }));
+                }
+                page.prepend_lines(lines);
             }
 
             Ok(page)
@@ -174,28 +421,66 @@ impl Preparer<'_> {
         if settings.is_empty() {
             return Ok(());
         }
+        if self.excluded(date) {
+            return Ok(());
+        }
 
         self.vault.update(&date, |mut page| {
             if settings.day_of_week {
-                page.insert_property("day", weekday(date));
+                page.insert_property("day", weekday(date, self.vault.config().locale()));
             }
             if settings.link_to_week {
-                page.insert_property("week", date.iso_week().to_link(self.vault));
+                page.insert_property("week", self.week_of(date).to_link(self.vault));
+            }
+            if settings.week_number {
+                page.insert_integer_property("week_number", i64::from(self.week_of(date).week()));
+            }
+            if settings.day_of_year {
+                page.insert_integer_property("day_of_year", i64::from(date.ordinal()));
             }
             if settings.link_to_month {
                 page.insert_property("month", Month::from(date).to_link(self.vault));
             }
             if settings.nav_link {
-                page.insert_property("next", date.next().to_link(self.vault));
-                page.insert_property("prev", date.prev().to_link(self.vault));
+                let mut next = date.next().to_link(self.vault);
+                let mut prev = date.prev().to_link(self.vault);
+                if settings.nav_title == NavTitle::Relative {
+                    let (tomorrow, yesterday) = relative_days(self.vault.config().locale());
+                    tomorrow.clone_into(&mut next.title);
+                    yesterday.clone_into(&mut prev.title);
+                }
+                page.insert_property("next", next);
+                page.insert_property("prev", prev);
+            }
+            if settings.aliases {
+                let mut alias = String::new();
+                match &settings.alias_format {
+                    Some(format) => write!(alias, "{}", date.format(format))
+                        .map_err(|_| anyhow!("Invalid alias format {format:?}"))?,
+                    None => alias = long_date(date, self.vault.config().locale()),
+                }
+                page.insert_sequence_property("aliases", [alias]);
             }
             if settings.events {
-                page.prepend_lines(
-                    self.vault
-                        .events()
-                        .filter(|ev| ev.matches(date))
-                        .map(|ev| &ev.content),
-                );
+                let mut events = self
+                    .vault
+                    .events()
+                    .filter(|ev| ev.matches(date) && ev.matches_tags(&settings.tags))
+                    .collect::<Vec<_>>();
+                events.sort_by_key(|ev| std::cmp::Reverse(ev.priority().unwrap_or(0)));
+                let mut lines = events
+                    .into_iter()
+                    .flat_map(|ev| event_lines(ev, date, self.vault, true))
+                    .collect::<Vec<_>>();
+                if settings.callout && !lines.is_empty() {
+                    lines = callout(settings.callout_type(), settings.callout_title(), lines);
+                }
+                // The callout is only valid as a whole, so it's always replaced between markers
+                if settings.markers || settings.callout {
+                    page.replace_marked_lines(EVENTS_START_MARKER, END_MARKER, lines);
+                } else {
+                    page.prepend_lines(lines);
+                }
             }
 
             Ok(page)
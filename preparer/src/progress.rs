@@ -0,0 +1,79 @@
+use chrono::NaiveDate;
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use log::LevelFilter;
+
+/// Layout of the bar, e.g. `[#######-----] 12/31 2025-01-12`
+const TEMPLATE: &str = "[{bar:30}] {pos}/{len} {msg}";
+
+/// Whether to show the progress: only on interactive terminals, when the logs are not sent to the
+/// journal nor disabled with `--quiet`
+pub fn enabled(interactive: bool, connected_to_journal: bool, level: LevelFilter) -> bool {
+    interactive && !connected_to_journal && level != LevelFilter::Off
+}
+
+/// Progress bar of the prepared days, drawn on stderr along with the logs
+#[derive(Debug)]
+pub struct Progress {
+    from: NaiveDate,
+    bar: ProgressBar,
+}
+
+impl Progress {
+    /// Bar added to the progress the logs are printed through, or hidden without it
+    pub fn new(from: NaiveDate, to: NaiveDate, progress: Option<&MultiProgress>) -> Self {
+        let total = (to - from).num_days().max(0).unsigned_abs() + 1;
+        let bar = progress.map_or_else(ProgressBar::hidden, |progress| {
+            progress.add(ProgressBar::new(total))
+        });
+        if let Ok(style) = ProgressStyle::with_template(TEMPLATE) {
+            bar.set_style(style.progress_chars("#-"));
+        }
+        bar.set_length(total);
+        Self { from, bar }
+    }
+
+    /// Mark the days up to the date as prepared
+    pub fn advance(&self, date: NaiveDate) {
+        let done = (date - self.from).num_days().max(0).unsigned_abs() + 1;
+        self.bar.set_message(date.to_string());
+        self.bar
+            .set_position(done.min(self.bar.length().unwrap_or(done)));
+    }
+
+    /// Leave the bar as is, below the logs
+    pub fn finish(&self) {
+        self.bar.abandon();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disabled_outside_terminals() {
+        assert!(enabled(true, false, LevelFilter::Error));
+        assert!(!enabled(false, false, LevelFilter::Error));
+        assert!(!enabled(true, true, LevelFilter::Error));
+        assert!(!enabled(true, false, LevelFilter::Off));
+    }
+
+    #[test]
+    fn count_days() {
+        let from = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let to = NaiveDate::from_ymd_opt(2025, 1, 31).unwrap();
+        let progress = Progress::new(from, to, None);
+        assert!(progress.bar.is_hidden());
+        assert_eq!(Some(31), progress.bar.length());
+
+        progress.advance(from);
+        assert_eq!(1, progress.bar.position());
+        progress.advance(NaiveDate::from_ymd_opt(2025, 1, 10).unwrap());
+        assert_eq!(10, progress.bar.position());
+        progress.advance(NaiveDate::from_ymd_opt(2025, 2, 10).unwrap());
+        assert_eq!(31, progress.bar.position());
+
+        let progress = Progress::new(from, from, None);
+        assert_eq!(Some(1), progress.bar.length());
+    }
+}
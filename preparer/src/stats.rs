@@ -0,0 +1,76 @@
+use chrono::NaiveDate;
+use std::collections::BTreeMap;
+use utils::events::recurrence::Frequency;
+use utils::events::Event;
+
+/// Summary of the events occurring between two dates, both inclusive
+#[derive(Debug, Eq, PartialEq)]
+pub struct Stats {
+    from: NaiveDate,
+    to: NaiveDate,
+    days: usize,
+    days_with_events: usize,
+    /// First day with the most events, along with their number
+    busiest_day: Option<(NaiveDate, usize)>,
+    /// Number of events occurring in the range, per frequency
+    frequencies: BTreeMap<Frequency, usize>,
+}
+
+impl Stats {
+    pub fn new<'a, I>(events: I, from: NaiveDate, to: NaiveDate) -> Self
+    where
+        I: IntoIterator<Item = &'a Event>,
+    {
+        let events = events
+            .into_iter()
+            .filter(|ev| ev.matches_range(from, to))
+            .collect::<Vec<_>>();
+
+        let mut frequencies = BTreeMap::new();
+        for event in &events {
+            *frequencies
+                .entry(event.recurrence().frequency())
+                .or_default() += 1;
+        }
+
+        let mut stats = Self {
+            from,
+            to,
+            days: 0,
+            days_with_events: 0,
+            busiest_day: None,
+            frequencies,
+        };
+        for date in from.iter_days().take_while(|date| *date <= to) {
+            let count = events.iter().filter(|ev| ev.matches(date)).count();
+            stats.days += 1;
+            if count > 0 {
+                stats.days_with_events += 1;
+            }
+            if count > stats.busiest_day.map_or(0, |(_, busiest)| busiest) {
+                stats.busiest_day = Some((date, count));
+            }
+        }
+        stats
+    }
+}
+
+impl std::fmt::Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Events from {} to {}", self.from, self.to)?;
+        writeln!(
+            f,
+            "Days with events: {}/{}",
+            self.days_with_events, self.days
+        )?;
+        match self.busiest_day {
+            Some((date, count)) => writeln!(f, "Busiest day: {date} ({count} events)")?,
+            None => writeln!(f, "Busiest day: none")?,
+        }
+        writeln!(f, "Events per frequency:")?;
+        for (frequency, count) in &self.frequencies {
+            writeln!(f, "- {}: {count}", frequency.name())?;
+        }
+        Ok(())
+    }
+}
@@ -1,6 +1,9 @@
 use crate::vault::Vault;
-use chrono::{Datelike, IsoWeek, NaiveDate};
-use utils::date::{Month, Year};
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+use utils::date::{Month, ToDateIterator, Week, Year};
+use utils::locale::{week_label, Locale};
+use utils::options::Period;
 
 #[derive(Debug, Clone, derive_more::Display)]
 #[display("[[/{path}|{title}]]")]
@@ -9,36 +12,99 @@ pub struct Link {
     pub title: String,
 }
 
+/// Visible title of the links
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LinkTitleStyle {
+    /// Last component of the page path, e.g. `January` for `2025/January`
+    #[default]
+    Leaf,
+    /// Whole page path, e.g. `2025/January`
+    Full,
+}
+
+/// Organization of the generated pages in the vault
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Layout {
+    /// Weeks and months in a folder per year, e.g. `2025/Week 02` and `2025/January`
+    #[default]
+    Nested,
+    /// All pages at the root, e.g. `Week 2025-02` and `2025-January`
+    Flat,
+}
+
 pub trait ToLink {
     fn to_link(self, vault: &Vault) -> Link;
 }
 impl<T: ToPageName> ToLink for T {
     fn to_link(self, vault: &Vault) -> Link {
         let path = vault.page_path(&self);
-        let title = if let Some((_, title)) = path.rsplit_once('/') {
-            title.to_owned()
-        } else {
-            path.clone()
+        let title = match (
+            self.link_title(vault),
+            vault.config().link_title_style(),
+            path.rsplit_once('/'),
+        ) {
+            (Some(title), _, _) => title,
+            (None, LinkTitleStyle::Leaf, Some((_, title))) => title.to_owned(),
+            _ => path.clone(),
         };
         Link { path, title }
     }
 }
 
+/// Placeholders of the `week_title` template, replaced at runtime rather than by a formatting
+/// macro: the zero-padded number of the week, its number and its year
+#[allow(clippy::literal_string_with_formatting_args)]
+const WEEK_TITLE_PLACEHOLDERS: [&str; 3] = ["{week:02}", "{week}", "{year}"];
+
+/// Visible title of the week from the template, replacing `{week}` by the number of the week,
+/// `{week:02}` by the zero-padded number and `{year}` by the year
+fn week_title(template: &str, week: Week) -> String {
+    let [padded_number, number, year] = WEEK_TITLE_PLACEHOLDERS;
+    template
+        .replace(padded_number, &format!("{:02}", week.week()))
+        .replace(number, &week.week().to_string())
+        .replace(year, &Year::from(week.year()).to_string())
+}
+
+/// Link to a note by its name, e.g. `[[Morning routine]]`
+#[derive(Debug, Clone, derive_more::Display)]
+#[display("[[{_0}]]")]
+pub struct NoteLink(pub String);
+
 #[derive(Debug, Clone, derive_more::Display)]
-#[display("!{link}")]
-pub struct Embedded {
-    pub link: Link,
+#[display("{prefix}{link}")]
+pub struct Embedded<L = Link> {
+    pub prefix: String,
+    pub link: L,
 }
 
-pub trait ToEmbedded {
-    fn into_embedded(self) -> Embedded;
+pub trait ToEmbedded: Sized {
+    fn into_embedded(self, vault: &Vault) -> Embedded<Self>;
 }
 impl ToEmbedded for Link {
-    fn into_embedded(self) -> Embedded {
-        Embedded { link: self }
+    fn into_embedded(self, vault: &Vault) -> Embedded {
+        Embedded {
+            prefix: vault.config().embed_prefix().to_owned(),
+            link: self,
+        }
+    }
+}
+impl ToEmbedded for NoteLink {
+    fn into_embedded(self, vault: &Vault) -> Embedded<Self> {
+        Embedded {
+            prefix: vault.config().embed_prefix().to_owned(),
+            link: self,
+        }
     }
 }
 
+/// Range of dates covered by the period, e.g. `2025-01-06 – 2025-01-12`
+pub fn date_range<T: ToDateIterator<Element = NaiveDate>>(period: &T) -> String {
+    format!("{} – {}", period.first(), period.last())
+}
+
 #[derive(Default, Debug, Clone, Copy)]
 pub enum PageKind {
     #[default]
@@ -63,6 +129,29 @@ impl From<String> for PageName {
 
 pub trait ToPageName {
     fn to_page_name(&self) -> PageName;
+
+    /// Page name in the flat layout, without any folder
+    fn to_flat_page_name(&self) -> PageName {
+        self.to_page_name()
+    }
+
+    /// Page name in the layout of the vault
+    fn vault_page_name(&self, vault: &Vault) -> PageName {
+        match vault.config().layout() {
+            Layout::Nested => self.to_page_name(),
+            Layout::Flat => self.to_flat_page_name(),
+        }
+    }
+
+    /// Visible title of the links to the page, instead of its path
+    fn link_title(&self, _vault: &Vault) -> Option<String> {
+        None
+    }
+
+    /// Kind of template of the page, along with the date replacing its placeholders
+    fn template(&self) -> Option<(Period, NaiveDate)> {
+        None
+    }
 }
 
 impl ToPageName for PageName {
@@ -71,37 +160,194 @@ impl ToPageName for PageName {
     }
 }
 
-impl ToPageName for IsoWeek {
+/// Page name of the week, e.g. `2025/Week 02`, or `Week 2025-02` in the flat layout
+fn week_page_name(week: Week, label: &str, layout: Layout) -> PageName {
+    let year = Year::from(week.year());
+    match layout {
+        Layout::Nested => format!("{year}/{label} {:02}", week.week()).into(),
+        Layout::Flat => format!("{label} {year}-{:02}", week.week()).into(),
+    }
+}
+
+impl ToPageName for Week {
     fn to_page_name(&self) -> PageName {
-        format!("{:04}/Week {:02}", self.year(), self.week()).into()
+        week_page_name(*self, week_label(Locale::default()), Layout::Nested)
+    }
+
+    fn vault_page_name(&self, vault: &Vault) -> PageName {
+        let config = vault.config();
+        week_page_name(*self, config.week_label(), config.layout())
+    }
+
+    fn template(&self) -> Option<(Period, NaiveDate)> {
+        Some((Period::Week, self.first()))
+    }
+
+    fn link_title(&self, vault: &Vault) -> Option<String> {
+        vault
+            .config()
+            .week_title()
+            .map(|template| week_title(template, *self))
+    }
+
+    fn to_flat_page_name(&self) -> PageName {
+        week_page_name(*self, week_label(Locale::default()), Layout::Flat)
     }
 }
 
 impl ToPageName for NaiveDate {
     fn to_page_name(&self) -> PageName {
         PageName {
-            name: format!("{:04}-{:02}-{:02}", self.year(), self.month(), self.day()),
+            name: format!(
+                "{}-{:02}-{:02}",
+                Year::from(self.year()),
+                self.month(),
+                self.day()
+            ),
             kind: PageKind::Journal,
         }
     }
+
+    fn template(&self) -> Option<(Period, NaiveDate)> {
+        Some((Period::Day, *self))
+    }
 }
 
 impl ToPageName for Month {
     fn to_page_name(&self) -> PageName {
         format!("{}/{}", self.year(), self.name()).into()
     }
+
+    fn to_flat_page_name(&self) -> PageName {
+        format!("{}-{}", self.year(), self.name()).into()
+    }
+
+    fn template(&self) -> Option<(Period, NaiveDate)> {
+        Some((Period::Month, self.first()))
+    }
 }
 
 impl ToPageName for Year {
     fn to_page_name(&self) -> PageName {
         self.to_string().into()
     }
+
+    fn template(&self) -> Option<(Period, NaiveDate)> {
+        Some((Period::Year, self.first().first()))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use utils::date::{Month, Year};
+    use anyhow::Result;
+    use assert_fs::prelude::*;
+    use utils::date::{Month, WeekNumbering, Year};
+
+    #[test]
+    fn link_title_style() -> Result<()> {
+        let temp_dir = assert_fs::TempDir::new()?;
+        let month = Month::from(NaiveDate::from_ymd_opt(2025, 1, 12).unwrap());
+
+        let vault = Vault::new(temp_dir.path().to_path_buf())?;
+        assert_eq!(
+            "[[/2025/January|January]]",
+            month.to_link(&vault).to_string()
+        );
+
+        temp_dir
+            .child("journal-preparation-config.md")
+            .write_str("```toml\nlink_title_style = \"full\"\n```\n")?;
+        let vault = Vault::new(temp_dir.path().to_path_buf())?;
+        assert_eq!(
+            "[[/2025/January|2025/January]]",
+            month.to_link(&vault).to_string()
+        );
+        assert_eq!(
+            "[[/2025|2025]]",
+            Year::from(2025).to_link(&vault).to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn week_link_title() -> Result<()> {
+        let temp_dir = assert_fs::TempDir::new()?;
+        let [padded_number, number, year] = WEEK_TITLE_PLACEHOLDERS;
+        let config = temp_dir.child("journal-preparation-config.md");
+        let week = Week::from(NaiveDate::from_ymd_opt(2025, 1, 12).unwrap().iso_week());
+
+        let vault = Vault::new(temp_dir.path().to_path_buf())?;
+        assert_eq!(
+            "[[/2025/Week 02|Week 02]]",
+            week.to_link(&vault).to_string()
+        );
+
+        config.write_str(&format!("```toml\nweek_title = \"Week {number}\"\n```\n"))?;
+        let vault = Vault::new(temp_dir.path().to_path_buf())?;
+        assert_eq!("[[/2025/Week 02|Week 2]]", week.to_link(&vault).to_string());
+
+        config.write_str(&format!(
+            "```toml\nweek_title = \"W{number}\"\nlink_title_style = \"full\"\n```\n"
+        ))?;
+        let vault = Vault::new(temp_dir.path().to_path_buf())?;
+        assert_eq!("[[/2025/Week 02|W2]]", week.to_link(&vault).to_string());
+
+        config.write_str(&format!(
+            "```toml\nweek_title = \"{year}-W{padded_number}\"\n```\n"
+        ))?;
+        let vault = Vault::new(temp_dir.path().to_path_buf())?;
+        assert_eq!(
+            "[[/2025/Week 02|2025-W02]]",
+            week.to_link(&vault).to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn week_page_label() -> Result<()> {
+        let temp_dir = assert_fs::TempDir::new()?;
+        let config = temp_dir.child("journal-preparation-config.md");
+        let week = Week::from(NaiveDate::from_ymd_opt(2025, 1, 12).unwrap().iso_week());
+
+        config.write_str("```toml\nweek_label = \"Semaine\"\n```\n")?;
+        let vault = Vault::new(temp_dir.path().to_path_buf())?;
+        assert_eq!("2025/Semaine 02", vault.page_path(&week));
+        assert_eq!(
+            "[[/2025/Semaine 02|Semaine 02]]",
+            week.to_link(&vault).to_string()
+        );
+
+        config.write_str("```toml\nweek_label = \"Woche\"\nlayout = \"flat\"\n```\n")?;
+        let vault = Vault::new(temp_dir.path().to_path_buf())?;
+        assert_eq!("Woche 2025-02", vault.page_path(&week));
+        assert_eq!(
+            "[[/Woche 2025-02|Woche 2025-02]]",
+            week.to_link(&vault).to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn week_date_range() {
+        let week = NaiveDate::from_ymd_opt(2025, 1, 8).unwrap().iso_week();
+        assert_eq!("2025-01-06 – 2025-01-12", date_range(&week));
+
+        let week = NaiveDate::from_ymd_opt(2025, 1, 31).unwrap().iso_week();
+        assert_eq!("2025-01-27 – 2025-02-02", date_range(&week));
+
+        let week = NaiveDate::from_ymd_opt(2025, 12, 31).unwrap().iso_week();
+        assert_eq!("2025-12-29 – 2026-01-04", date_range(&week));
+
+        let week = Week::new(
+            NaiveDate::from_ymd_opt(2025, 12, 31).unwrap(),
+            WeekNumbering::Us,
+        );
+        assert_eq!("2025-12-28 – 2026-01-03", date_range(&week));
+    }
 
     mod page_name {
         use super::*;
@@ -115,12 +361,19 @@ mod tests {
 
         #[test]
         fn week() {
-            let week = NaiveDate::from_ymd_opt(2025, 1, 12)
-                .unwrap()
-                .iso_week()
-                .to_page_name();
+            let date = NaiveDate::from_ymd_opt(2025, 1, 12).unwrap();
+            let week = Week::from(date.iso_week()).to_page_name();
             assert_eq!("2025/Week 02", week.name);
             assert!(matches!(week.kind, PageKind::Default));
+
+            let week = Week::new(date, WeekNumbering::Us).to_page_name();
+            assert_eq!("2025/Week 03", week.name);
+
+            let date = NaiveDate::from_ymd_opt(2025, 12, 29).unwrap();
+            assert_eq!(
+                "2026/Week 01",
+                Week::new(date, WeekNumbering::Us).to_page_name().name
+            );
         }
 
         #[test]
@@ -136,5 +389,38 @@ mod tests {
             assert_eq!("2025", year.name);
             assert!(matches!(year.kind, PageKind::Default));
         }
+
+        #[test]
+        fn flat() {
+            let date = NaiveDate::from_ymd_opt(2025, 1, 12).unwrap();
+            assert_eq!("2025-01-12", date.to_flat_page_name().name);
+            assert!(matches!(date.to_flat_page_name().kind, PageKind::Journal));
+            assert_eq!(
+                "Week 2025-02",
+                Week::from(date.iso_week()).to_flat_page_name().name
+            );
+            assert_eq!("2025-January", Month::from(date).to_flat_page_name().name);
+            assert_eq!("2025", Year::from(2025).to_flat_page_name().name);
+        }
+
+        #[test]
+        fn years_before_1000() {
+            let date = NaiveDate::from_ymd_opt(850, 3, 12).unwrap();
+            assert_eq!("0850-03-12", date.to_page_name().name);
+            assert_eq!(
+                "0850/Week 10",
+                Week::from(date.iso_week()).to_page_name().name
+            );
+            assert_eq!("0850/March", Month::from(date).to_page_name().name);
+            assert_eq!("0850", Year::from(850).to_page_name().name);
+        }
+
+        #[test]
+        fn negative_years() {
+            let date = NaiveDate::from_ymd_opt(-50, 3, 12).unwrap();
+            assert_eq!("-0050-03-12", date.to_page_name().name);
+            assert_eq!("-0050/March", Month::from(date).to_page_name().name);
+            assert_eq!("-0050", Year::from(-50).to_page_name().name);
+        }
     }
 }
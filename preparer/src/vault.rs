@@ -1,7 +1,14 @@
 use crate::utils::{PageKind, PageName, ToPageName};
-use anyhow::{Context, Result};
+use anyhow::Result;
+use indicatif::MultiProgress;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use utils::date::replace_date_tokens;
+use utils::error::PrepareError;
 use utils::events::Event;
+use utils::options::Period;
 use utils::page::Page;
 
 pub mod config;
@@ -10,21 +17,214 @@ pub use config::Config;
 /// A vault represents the whole folder with all the documents, e.g. the obsidian folder (which
 /// they name a vault)
 #[derive(Debug)]
+// The flags come from independent command line options, so we really need a bool
+#[allow(clippy::struct_excessive_bools)]
 pub struct Vault {
     config: Config,
     events: Vec<Event>,
+    skip_existing: bool,
+    merge_config: bool,
+    exclude_weekends: bool,
+    no_properties: bool,
+    append_only: bool,
+    preview: bool,
+    inputs_modified: Option<SystemTime>,
+    post_hook: Option<String>,
+    written_pages: Cell<usize>,
+    progress: Option<MultiProgress>,
+    clean: bool,
+    force: bool,
+    page_templates: Vec<(Period, PathBuf)>,
+    output_dir: Option<PathBuf>,
+    /// Pages read during the run, by path, so they are not read again until written
+    pages: RefCell<HashMap<PathBuf, Page>>,
 }
 
 impl Vault {
     pub fn new(path: PathBuf) -> Result<Self> {
         if !path.exists() {
             std::fs::create_dir_all(path.as_path())
-                .with_context(|| format!("creating dir {}", path.display()))?;
+                .map_err(|e| PrepareError::CreatingDir(e, path.clone()))?;
         }
         let config = Config::new(path)?;
-        let events = config.read_events()?;
+        let events = config.read_events(false)?;
+
+        Ok(Self {
+            config,
+            events,
+            skip_existing: false,
+            merge_config: false,
+            exclude_weekends: false,
+            no_properties: false,
+            append_only: false,
+            preview: false,
+            inputs_modified: None,
+            post_hook: None,
+            written_pages: Cell::new(0),
+            progress: None,
+            clean: false,
+            force: false,
+            page_templates: vec![],
+            output_dir: None,
+            pages: RefCell::default(),
+        })
+    }
+
+    /// Never modify pages that already exist, only create missing ones
+    #[must_use]
+    pub const fn with_skip_existing(mut self, skip_existing: bool) -> Self {
+        self.skip_existing = skip_existing;
+        self
+    }
+
+    /// Union the configuration file settings with the command line options instead of only using
+    /// them for the pages left to their defaults
+    #[must_use]
+    pub const fn with_merge_config(mut self, merge_config: bool) -> Self {
+        self.merge_config = merge_config;
+        self
+    }
+
+    /// Do not prepare the day pages of the weekends, in addition to the configuration file
+    #[must_use]
+    pub const fn with_exclude_weekends(mut self, exclude_weekends: bool) -> Self {
+        self.exclude_weekends = exclude_weekends;
+        self
+    }
+
+    /// Do not add properties to the pages, only their content
+    #[must_use]
+    pub const fn with_no_properties(mut self, no_properties: bool) -> Self {
+        self.no_properties = no_properties;
+        self
+    }
+
+    /// Keep the body of the existing pages verbatim, only appending the new generated lines
+    #[must_use]
+    pub const fn with_append_only(mut self, append_only: bool) -> Self {
+        self.append_only = append_only;
+        self
+    }
+
+    /// Print the pages to stdout instead of writing them
+    #[must_use]
+    pub const fn with_preview(mut self, preview: bool) -> Self {
+        self.preview = preview;
+        self
+    }
+
+    /// Do not modify pages modified after the configuration and event files, as they are
+    /// already up to date
+    pub fn with_since_modified(mut self, since_modified: bool) -> Result<Self> {
+        self.inputs_modified = if since_modified {
+            Some(self.config.inputs_modified()?)
+        } else {
+            None
+        };
+        Ok(self)
+    }
+
+    /// Shell command to run once all the pages are written, instead of the one from the
+    /// configuration file
+    #[must_use]
+    pub fn with_post_hook(mut self, post_hook: Option<String>) -> Self {
+        self.post_hook = post_hook;
+        self
+    }
+
+    /// Remove the pages instead of updating them, only if they contain nothing but the generated
+    /// content unless forced
+    #[must_use]
+    pub const fn with_clean(mut self, clean: bool, force: bool) -> Self {
+        self.clean = clean;
+        self.force = force;
+        self
+    }
+
+    /// Templates of the new pages, instead of the ones from the configuration file
+    #[must_use]
+    pub fn with_page_templates(mut self, page_templates: Vec<(Period, PathBuf)>) -> Self {
+        self.page_templates = page_templates;
+        self
+    }
+
+    /// Fail when an event file, event directory or page template is configured but does not exist,
+    /// or when an event file is invalid, instead of ignoring it
+    pub fn with_strict(mut self, strict: bool) -> Result<Self> {
+        if strict {
+            self.config.check_event_files()?;
+            self.events = self.config.read_events(true)?;
+            for kind in <Period as clap::ValueEnum>::value_variants() {
+                if let Some(template) = self.page_template(*kind) {
+                    if !template.exists() {
+                        return Err(PrepareError::TemplateNotFound(template).into());
+                    }
+                }
+            }
+        }
+        Ok(self)
+    }
+
+    /// Write the pages in this directory instead of the vault, the configuration and events still
+    /// being read from the vault
+    #[must_use]
+    pub fn with_output_dir(mut self, output_dir: Option<PathBuf>) -> Self {
+        self.output_dir = output_dir;
+        self
+    }
+
+    /// Show the progress of the preparation on stderr, along with the logs printed through it
+    #[must_use]
+    pub fn with_progress(mut self, progress: Option<MultiProgress>) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    /// Run the post hook, if any, with the number of written pages in `PREPARER_CHANGED_FILES`
+    pub fn run_post_hook(&self) -> Result<()> {
+        let Some(post_hook) = self
+            .post_hook
+            .as_deref()
+            .or_else(|| self.config.post_hook())
+        else {
+            return Ok(());
+        };
+
+        log::info!("Running post hook {post_hook:?}");
+        let status = std::process::Command::new("sh")
+            .arg("-c")
+            .arg(post_hook)
+            .current_dir(self.path())
+            .env("PREPARER_CHANGED_FILES", self.written_pages().to_string())
+            .status()
+            .map_err(|e| PrepareError::PostHook(e, post_hook.to_owned()))?;
+
+        if status.success() {
+            log::info!("Post hook {status}");
+        } else {
+            log::warn!("Post hook {status}");
+        }
+
+        Ok(())
+    }
+
+    /// Number of pages written or removed so far
+    pub const fn written_pages(&self) -> usize {
+        self.written_pages.get()
+    }
+
+    pub const fn progress(&self) -> Option<&MultiProgress> {
+        self.progress.as_ref()
+    }
 
-        Ok(Self { config, events })
+    /// Whether the day pages of the weekends are left out, from the command line or the
+    /// configuration file
+    pub const fn exclude_weekends(&self) -> bool {
+        self.exclude_weekends || self.config.exclude_weekends()
+    }
+
+    pub const fn merge_config(&self) -> bool {
+        self.merge_config
     }
 
     pub fn path(&self) -> &Path {
@@ -40,39 +240,172 @@ impl Vault {
     }
 
     pub fn page_path<T: ToPageName>(&self, object: &T) -> String {
-        let PageName { name, kind } = object.to_page_name();
+        let PageName { name, kind } = object.vault_page_name(self);
         match kind {
             PageKind::Journal => {
-                if let Some(journals_folder) = self.config.journals_folder() {
-                    journals_folder.to_owned() + name.as_str()
-                } else {
+                let folder = self
+                    .config
+                    .journals_folder()
+                    .unwrap_or_default()
+                    .split('/')
+                    .filter(|component| !component.is_empty())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                if folder.is_empty() {
                     name
+                } else {
+                    format!("{folder}/{name}")
                 }
             }
             PageKind::Default => name,
         }
     }
 
+    /// Directory the pages are written to, the vault itself unless configured otherwise
+    pub fn output_dir(&self) -> &Path {
+        self.output_dir.as_deref().unwrap_or_else(|| self.path())
+    }
+
     pub fn page_file_path<T: ToPageName>(&self, page: &T) -> PathBuf {
-        self.path().join(format!("{}.md", self.page_path(page)))
+        self.output_dir()
+            .join(format!("{}.md", self.page_path(page)))
     }
 
-    pub fn update<F, T>(&self, page: &T, f: F) -> Result<()>
+    pub fn page_exists<T: ToPageName>(&self, page: &T) -> bool {
+        self.page_file_path(page).exists()
+    }
+
+    /// Template of the new pages of the kind, from the command line or the configuration file
+    pub fn page_template(&self, kind: Period) -> Option<PathBuf> {
+        self.page_templates
+            .iter()
+            .rev()
+            .find(|(template_kind, _)| *template_kind == kind)
+            .map(|(_, file)| file.clone())
+            .or_else(|| self.config.page_template(kind))
+    }
+
+    /// Start the new page from its template, if any
+    fn seed<T: ToPageName>(&self, object: &T, page: &mut Page) -> Result<()> {
+        let Some((kind, date)) = object.template() else {
+            return Ok(());
+        };
+        if page.exists() {
+            return Ok(());
+        }
+        let Some(template) = self.page_template(kind) else {
+            return Ok(());
+        };
+
+        let text = std::fs::read_to_string(&template)
+            .map_err(|e| PrepareError::ReadingFile(e, template.clone()))?;
+        page.set_content(&replace_date_tokens(&text, date))
+            .map_err(PrepareError::from)?;
+        Ok(())
+    }
+
+    /// Read the page, unless it was already read and not written since during the run
+    fn read_page(&self, path: &Path) -> Result<Page> {
+        if let Some(page) = self.pages.borrow().get(path) {
+            log::debug!("Using the page {} read before", path.display());
+            return Ok(page.clone());
+        }
+        let page = Page::read(path.to_path_buf(), self.config.frontmatter_delimiter())
+            .map_err(PrepareError::from)?;
+        self.pages
+            .borrow_mut()
+            .insert(path.to_path_buf(), page.clone());
+        Ok(page)
+    }
+
+    pub fn update<F, T>(&self, object: &T, f: F) -> Result<()>
     where
         T: ToPageName,
         F: FnOnce(Page) -> Result<Page>,
     {
-        let path = self.page_file_path(page);
+        let path = self.page_file_path(object);
+        if self.clean {
+            return self.clean(object, &path, f);
+        }
+        if self.skip_existing && self.page_exists(object) {
+            log::info!("Skipping existing page {}", path.display());
+            return Ok(());
+        }
+        if self.inputs_modified.is_some_and(|inputs_modified| {
+            std::fs::metadata(&path)
+                .and_then(|metadata| metadata.modified())
+                .is_ok_and(|modified| modified >= inputs_modified)
+        }) {
+            log::info!("Skipping up to date page {}", path.display());
+            return Ok(());
+        }
         log::info!("Updating page {}", path.display());
 
-        let mut page = f(Page::try_from(path)?)?;
+        let mut page = self.read_page(&path)?;
+        self.seed(object, &mut page)?;
+        if let Some(separator) = self.config.content_separator() {
+            page.set_separator(separator);
+        }
+        if self.no_properties {
+            page.disable_properties();
+        }
+        let original = self.append_only.then(|| page.clone());
+        let mut page = f(page)?;
+        if let Some(original) = &original {
+            page.restore_body(original);
+        }
+        if self.preview {
+            print!("{}", page.text());
+            return Ok(());
+        }
 
         if page.modified() {
-            page.write()?;
+            self.pages.borrow_mut().remove(&path);
+            page.write().map_err(PrepareError::from)?;
+            self.written_pages.set(self.written_pages.get() + 1);
         }
 
         Ok(())
     }
+
+    /// Remove the page if it only contains what `f` would generate in an empty page, or
+    /// unconditionally if forced
+    fn clean<F, T>(&self, object: &T, path: &Path, f: F) -> Result<()>
+    where
+        T: ToPageName,
+        F: FnOnce(Page) -> Result<Page>,
+    {
+        if !path.exists() {
+            return Ok(());
+        }
+
+        let mut page = self.read_page(path)?;
+        if !self.force {
+            let mut generated = Page::empty(path.to_path_buf());
+            generated.set_delimiter(self.config.frontmatter_delimiter());
+            self.seed(object, &mut generated)?;
+            if let Some(separator) = self.config.content_separator() {
+                generated.set_separator(separator);
+            }
+            if self.no_properties {
+                generated.disable_properties();
+            }
+            if !page.same_content(&f(generated)?) {
+                log::warn!(
+                    "Not removing page {} with other content, use --force to remove it anyway",
+                    path.display()
+                );
+                return Ok(());
+            }
+        }
+
+        log::info!("Removing page {}", path.display());
+        self.pages.borrow_mut().remove(path);
+        page.remove().map_err(PrepareError::from)?;
+        self.written_pages.set(self.written_pages.get() + 1);
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -138,6 +471,16 @@ mod tests {
             })
         );
 
+        let output_dir = temp_dir.child("staging");
+        let vault = vault.with_output_dir(Some(output_dir.to_path_buf()));
+        assert_eq!(
+            output_dir.child("daily-notes/page.md").path(),
+            vault.page_file_path(&PageName {
+                name: "page".to_owned(),
+                kind: PageKind::Journal
+            })
+        );
+
         Ok(())
     }
 
@@ -186,6 +529,74 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn page_exists() -> Result<()> {
+        let temp_dir = assert_fs::TempDir::new()?;
+        let vault = Vault::new(temp_dir.path().to_path_buf())?;
+        let page = |name: &str| PageName {
+            name: name.to_owned(),
+            kind: PageKind::Default,
+        };
+
+        temp_dir.child("2025/January.md").write_str("")?;
+        assert!(vault.page_exists(&page("2025/January")));
+        assert!(!vault.page_exists(&page("2025/February")));
+        // Not a page
+        assert!(!vault.page_exists(&page("2025")));
+
+        Ok(())
+    }
+
+    #[test]
+    fn page_path_journals_folder() -> Result<()> {
+        let temp_dir = assert_fs::TempDir::new()?;
+        let page = PageName {
+            name: "page".to_owned(),
+            kind: PageKind::Journal,
+        };
+
+        for (journals_folder, expected) in [
+            ("Daily", "Daily/page"),
+            ("Daily/", "Daily/page"),
+            ("Daily//Notes/", "Daily/Notes/page"),
+            ("/Daily", "Daily/page"),
+            ("", "page"),
+        ] {
+            temp_dir
+                .child("journal-preparation-config.md")
+                .write_str(&format!(
+                    "```toml\njournals_folder = \"{journals_folder}\"\n```\n"
+                ))?;
+            let vault = Vault::new(temp_dir.path().to_path_buf())?;
+            assert_eq!(expected, vault.page_path(&page), "{journals_folder:?}");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn page_path_flat_layout() -> Result<()> {
+        use chrono::{Datelike, NaiveDate};
+        use utils::date::{Month, Week, Year};
+
+        let temp_dir = assert_fs::TempDir::new()?;
+        temp_dir
+            .child("journal-preparation-config.md")
+            .write_str("```toml\nlayout = \"flat\"\njournals_folder = \"Daily\"\n```\n")?;
+        let vault = Vault::new(temp_dir.path().to_path_buf())?;
+        let date = NaiveDate::from_ymd_opt(2025, 1, 12).unwrap();
+
+        assert_eq!("Daily/2025-01-12", vault.page_path(&date));
+        assert_eq!(
+            "Week 2025-02",
+            vault.page_path(&Week::from(date.iso_week()))
+        );
+        assert_eq!("2025-January", vault.page_path(&Month::from(date)));
+        assert_eq!("2025", vault.page_path(&Year::from(2025)));
+
+        Ok(())
+    }
+
     #[test]
     fn creates_vault() -> Result<()> {
         let temp_dir = assert_fs::TempDir::new()?.child("dir");
@@ -224,4 +635,120 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn update_cached() -> Result<()> {
+        let cached_dir = assert_fs::TempDir::new()?;
+        let uncached_dir = assert_fs::TempDir::new()?;
+        let cached = Vault::new(cached_dir.path().to_path_buf())?;
+        let name: PageName = "foo".to_string().into();
+        let update = |vault: &Vault, line: &str| {
+            vault.update(&name, |mut page| {
+                page.prepend_line(line);
+                page.insert_property("last", line);
+                Ok(page)
+            })
+        };
+
+        for line in ["World", "Hello", "Hello"] {
+            update(&cached, line)?;
+            update(&Vault::new(uncached_dir.path().to_path_buf())?, line)?;
+        }
+
+        let path = cached.page_file_path(&name);
+        assert_eq!(
+            std::fs::read_to_string(uncached_dir.child("foo.md").path())?,
+            std::fs::read_to_string(&path)?
+        );
+        assert_eq!(
+            "---\nlast: Hello\n---\nHello\nWorld\n",
+            std::fs::read_to_string(&path)?
+        );
+
+        // The last update did not write the page, so the next one uses the page read before
+        assert!(cached.pages.borrow().contains_key(&path));
+        std::fs::write(&path, "Changed\n")?;
+        cached.update(&name, |page| {
+            assert_eq!("---\nlast: Hello\n---\nHello\nWorld\n", page.text());
+            Ok(page)
+        })?;
+
+        // Writing the page invalidates it
+        update(&cached, "Again")?;
+        assert!(!cached.pages.borrow().contains_key(&path));
+
+        Ok(())
+    }
+
+    #[test]
+    fn update_since_modified() -> Result<()> {
+        use std::time::Duration;
+
+        let temp_dir = assert_fs::TempDir::new()?;
+        let events = temp_dir.child("events/recurring.md");
+        events.write_str("")?;
+        let set_events_modified = |modified| -> Result<()> {
+            std::fs::File::options()
+                .write(true)
+                .open(events.path())?
+                .set_modified(modified)?;
+            Ok(())
+        };
+        let name: PageName = "foo".to_string().into();
+        let path = temp_dir.child("foo.md");
+
+        // Untouched event file, existing pages are up to date
+        set_events_modified(SystemTime::now() - Duration::from_hours(1))?;
+        let vault = Vault::new(temp_dir.path().to_path_buf())?.with_since_modified(true)?;
+
+        vault.update(&name, |mut page| {
+            page.prepend_line("World");
+            Ok(page)
+        })?;
+        path.assert("World\n");
+
+        vault.update(&name, |mut page| {
+            page.prepend_line("Hello");
+            Ok(page)
+        })?;
+        path.assert("World\n");
+
+        // Touched event file, pages are regenerated
+        set_events_modified(SystemTime::now() + Duration::from_hours(1))?;
+        let vault = Vault::new(temp_dir.path().to_path_buf())?.with_since_modified(true)?;
+
+        vault.update(&name, |mut page| {
+            page.prepend_line("Hello");
+            Ok(page)
+        })?;
+        path.assert("Hello\nWorld\n");
+
+        Ok(())
+    }
+
+    #[test]
+    fn update_skip_existing() -> Result<()> {
+        let temp_dir = assert_fs::TempDir::new()?;
+        let vault = Vault::new(temp_dir.path().to_path_buf())?.with_skip_existing(true);
+        let name: PageName = "foo".to_string().into();
+
+        vault.update(&name, |mut page| {
+            page.prepend_line("World");
+            Ok(page)
+        })?;
+
+        let path = vault.page_file_path(&name);
+        let content = std::fs::read_to_string(&path)?;
+        assert_eq!(content, "World\n");
+
+        vault.update(&name, |mut page| {
+            page.prepend_line("Hello");
+            Ok(page)
+        })?;
+
+        let content = std::fs::read_to_string(&path)?;
+        assert_eq!(content, "World\n");
+
+        Ok(())
+    }
 }
@@ -1,11 +1,73 @@
+use crate::utils::{Layout, LinkTitleStyle};
 use anyhow::{Context, Result};
+use chrono::{NaiveDate, Weekday};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::path::{Path, PathBuf};
-use utils::content::Entry;
-use utils::events::Event;
-use utils::options::PageSettings;
-use utils::page::{Page, PageError};
+use std::time::SystemTime;
+use utils::clock::{Clock, SystemClock};
+use utils::content::{Entry, DEFAULT_DELIMITER};
+use utils::date::WeekNumbering;
+use utils::error::{ConfigError, PrepareError};
+use utils::events::recurrence::DEFAULT_WEEKEND_DAYS;
+use utils::events::{is_event_block, DateRange, Event};
+use utils::locale::{week_label, Locale};
+use utils::options::{PageSettings, Period};
+use utils::page::Page;
+
+const CONFIG_PAGE: &str = "journal-preparation-config.md";
+const DAILY_NOTES_CONFIG: &str = ".obsidian/daily-notes.json";
+const DEFAULT_EVENT_FILE: &str = "events/recurring.md";
+const DEFAULT_EVENT_BLOCK_LANG: &str = "toml";
+/// Obsidian embeds a note with `![[Note]]`
+const DEFAULT_EMBED_PREFIX: &str = "!";
+
+const CONFIG_PAGE_TEMPLATE: &str = r#"```toml
+# Folder of the day pages, defaults to the one configured for the daily notes
+# journals_folder = "journals"
+# Additional pages containing events
+# event_files = ["events/birthdays.md"]
+
+[day]
+# Add day of the week as page property
+day_of_week = true
+# Add link to the week as page property
+link_to_week = true
+# Add link to the month page as page property
+link_to_month = true
+# Add link to next and previous day as page property
+nav_link = true
+# Add matching events content in the page
+events = true
+
+[week]
+# Embeds days of the week in the page
+week = true
+# Add link to the month page as page property
+link_to_month = true
+# Add link to next and previous week as page property
+nav_link = true
+
+[month]
+# Embeds days of the month (grouped by week) in the page
+month = true
+# Add link to next and previous month as page property
+nav_link = true
+
+[year]
+# Add links to the months in the page
+month = true
+# Add link to next and previous year as page property
+nav_link = true
+```
+"#;
+
+const EVENT_FILE_TEMPLATE: &str = r#"```toml
+frequency = "weekly"
+weekdays = ["Monday"]
+content = "- [ ] Plan the week"
+```
+"#;
 
 #[derive(Debug)]
 pub struct Config {
@@ -13,9 +75,28 @@ pub struct Config {
     journals_folder: Option<String>,
     settings: PageSettings,
     event_files: Vec<String>,
+    /// Default event file read without being configured, so it may not exist
+    implicit_event_file: Option<String>,
+    event_dirs: Vec<String>,
+    holidays: Vec<NaiveDate>,
+    default_validity: DateRange,
+    post_hook: Option<String>,
+    link_title_style: LinkTitleStyle,
+    week_title: Option<String>,
+    week_label: String,
+    content_separator: Option<String>,
+    layout: Layout,
+    event_block_lang: String,
+    week_numbering: WeekNumbering,
+    frontmatter_delimiter: String,
+    embed_prefix: String,
+    exclude_weekends: bool,
+    weekend_days: Vec<Weekday>,
+    page_templates: PageTemplates,
+    locale: Locale,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct SerdeConfig {
     #[serde(default)]
     journals_folder: Option<String>,
@@ -23,22 +104,95 @@ pub struct SerdeConfig {
     settings: PageSettings,
     #[serde(default)]
     event_files: Vec<String>,
+    #[serde(default)]
+    event_dirs: Vec<String>,
+    #[serde(default)]
+    default_event_file: Option<DefaultEventFile>,
+    #[serde(default)]
+    holidays: Vec<NaiveDate>,
+    #[serde(default)]
+    default_validity: Option<DateRange>,
+    #[serde(default)]
+    post_hook: Option<String>,
+    #[serde(default)]
+    link_title_style: Option<LinkTitleStyle>,
+    #[serde(default)]
+    week_title: Option<String>,
+    #[serde(default)]
+    week_label: Option<String>,
+    #[serde(default)]
+    content_separator: Option<String>,
+    #[serde(default)]
+    layout: Option<Layout>,
+    #[serde(default)]
+    event_block_lang: Option<String>,
+    #[serde(default)]
+    week_numbering: Option<WeekNumbering>,
+    #[serde(default)]
+    frontmatter_delimiter: Option<String>,
+    #[serde(default)]
+    embed_prefix: Option<String>,
+    #[serde(default)]
+    exclude_weekends: Option<bool>,
+    #[serde(default)]
+    weekend_days: Option<Vec<Weekday>>,
+    #[serde(default)]
+    page_templates: PageTemplates,
+    #[serde(default)]
+    locale: Option<Locale>,
 }
 
-impl Default for SerdeConfig {
-    fn default() -> Self {
+/// Templates of the new pages, relative to the vault
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct PageTemplates {
+    #[serde(default)]
+    day: Option<String>,
+    #[serde(default)]
+    week: Option<String>,
+    #[serde(default)]
+    month: Option<String>,
+    #[serde(default)]
+    year: Option<String>,
+}
+
+impl PageTemplates {
+    fn get(&self, kind: Period) -> Option<&str> {
+        match kind {
+            Period::Day => self.day.as_deref(),
+            Period::Week => self.week.as_deref(),
+            Period::Month => self.month.as_deref(),
+            Period::Year => self.year.as_deref(),
+        }
+    }
+
+    fn merge(self, other: Self) -> Self {
         Self {
-            journals_folder: None,
-            settings: PageSettings::default(),
-            event_files: vec!["events/recurring.md".to_owned()],
+            day: self.day.or(other.day),
+            week: self.week.or(other.week),
+            month: self.month.or(other.month),
+            year: self.year.or(other.year),
         }
     }
 }
 
-#[derive(Debug, derive_more::From, derive_more::Display, derive_more::Error)]
-pub enum ConfigError {
-    ReadingFile(PageError),
-    Toml(toml::de::Error),
+/// Event file always read in addition to `event_files`
+///
+/// Either `false` to disable it, or the path of the file to use instead of `events/recurring.md`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum DefaultEventFile {
+    Enabled(bool),
+    Path(String),
+}
+
+impl DefaultEventFile {
+    fn path(&self) -> Option<&str> {
+        match self {
+            Self::Enabled(true) => Some(DEFAULT_EVENT_FILE),
+            Self::Enabled(false) => None,
+            Self::Path(path) => Some(path),
+        }
+    }
 }
 
 impl TryFrom<PathBuf> for Config {
@@ -49,7 +203,7 @@ impl TryFrom<PathBuf> for Config {
             return Ok((path, SerdeConfig::default()).into());
         }
 
-        let page = Page::try_from(path.join("journal-preparation-config.md").as_path())?;
+        let page = Page::try_from(path.join(CONFIG_PAGE).as_path())?;
         let mut configs = Vec::<SerdeConfig>::new();
 
         for entry in page.entries() {
@@ -60,39 +214,104 @@ impl TryFrom<PathBuf> for Config {
             }
         }
 
+        // The defaults are only applied once all the blocks are merged, so they never take
+        // precedence over any block
         let merged_configs = configs
             .into_iter()
-            .fold(SerdeConfig::default(), |config_a, config_b| {
-                config_a.merge(config_b)
-            });
+            .reduce(SerdeConfig::merge)
+            .unwrap_or_default();
 
         Ok((path, merged_configs).into())
     }
 }
 
 impl From<(PathBuf, SerdeConfig)> for Config {
-    fn from((path, config): (PathBuf, SerdeConfig)) -> Self {
+    fn from((path, mut config): (PathBuf, SerdeConfig)) -> Self {
+        let implicit = config.default_event_file.is_none();
+        let default_event_file = config
+            .default_event_file
+            .unwrap_or(DefaultEventFile::Enabled(true));
+        let mut implicit_event_file = None;
+        if let Some(file) = default_event_file.path() {
+            if config.event_files.iter().all(|f| f != file) {
+                config.event_files.insert(0, file.to_owned());
+                if implicit {
+                    implicit_event_file = Some(file.to_owned());
+                }
+            }
+        }
+
+        let locale = config.locale.unwrap_or_default();
         Self {
             path,
             journals_folder: config.journals_folder,
             event_files: config.event_files,
+            implicit_event_file,
+            event_dirs: config.event_dirs,
             settings: config.settings,
+            holidays: config.holidays,
+            default_validity: config.default_validity.unwrap_or_default(),
+            post_hook: config.post_hook,
+            link_title_style: config.link_title_style.unwrap_or_default(),
+            week_title: config.week_title,
+            week_label: config
+                .week_label
+                .unwrap_or_else(|| week_label(locale).to_owned()),
+            content_separator: config.content_separator,
+            layout: config.layout.unwrap_or_default(),
+            event_block_lang: config
+                .event_block_lang
+                .unwrap_or_else(|| DEFAULT_EVENT_BLOCK_LANG.to_owned()),
+            week_numbering: config.week_numbering.unwrap_or_default(),
+            frontmatter_delimiter: config
+                .frontmatter_delimiter
+                .unwrap_or_else(|| DEFAULT_DELIMITER.to_owned()),
+            embed_prefix: config
+                .embed_prefix
+                .unwrap_or_else(|| DEFAULT_EMBED_PREFIX.to_owned()),
+            exclude_weekends: config.exclude_weekends.unwrap_or_default(),
+            weekend_days: config
+                .weekend_days
+                .unwrap_or_else(|| DEFAULT_WEEKEND_DAYS.to_vec()),
+            page_templates: config.page_templates,
+            locale,
         }
     }
 }
 
 impl Config {
     pub fn new(path: PathBuf) -> Result<Self> {
-        let mut config = match Self::try_from(path) {
-            Ok(config) => config,
-            Err(e) => Err(e)?,
-        };
+        let mut config = Self::try_from(path).map_err(PrepareError::from)?;
 
         config.read_daily_notes_config()?;
 
         Ok(config)
     }
 
+    /// Create the configuration page and the default event file with examples, unless they
+    /// already exist
+    pub fn init(path: &Path) -> Result<()> {
+        for (file, template) in [
+            (CONFIG_PAGE, CONFIG_PAGE_TEMPLATE),
+            (DEFAULT_EVENT_FILE, EVENT_FILE_TEMPLATE),
+        ] {
+            let file_path = path.join(file);
+            if file_path.exists() {
+                log::info!("Not overwriting existing {file:?}");
+                continue;
+            }
+            if let Some(parent) = file_path.parent() {
+                std::fs::create_dir_all(parent)
+                    .map_err(|e| PrepareError::CreatingDir(e, parent.to_path_buf()))?;
+            }
+            std::fs::write(&file_path, template)
+                .map_err(|e| PrepareError::WritingFile(e, file_path.clone()))?;
+            log::info!("Created {file:?}");
+        }
+
+        Ok(())
+    }
+
     pub fn path(&self) -> &Path {
         &self.path
     }
@@ -101,20 +320,84 @@ impl Config {
         self.journals_folder.as_deref()
     }
 
+    /// Shell command to run once all the pages are written
+    pub fn post_hook(&self) -> Option<&str> {
+        self.post_hook.as_deref()
+    }
+
+    /// Line separating the generated lines from the rest of the pages
+    pub fn content_separator(&self) -> Option<&str> {
+        self.content_separator.as_deref()
+    }
+
+    pub const fn layout(&self) -> Layout {
+        self.layout
+    }
+
+    pub const fn link_title_style(&self) -> LinkTitleStyle {
+        self.link_title_style
+    }
+
+    /// Visible title of the links to the weeks, e.g. `W{week}`
+    pub fn week_title(&self) -> Option<&str> {
+        self.week_title.as_deref()
+    }
+
+    /// Label of the weeks in their page names, e.g. `Week` for `2025/Week 02`
+    pub fn week_label(&self) -> &str {
+        &self.week_label
+    }
+
+    pub const fn week_numbering(&self) -> WeekNumbering {
+        self.week_numbering
+    }
+
+    /// Line before and after the properties of the pages
+    pub fn frontmatter_delimiter(&self) -> &str {
+        &self.frontmatter_delimiter
+    }
+
+    /// Characters before the links to embed the pages, e.g. `!` for `![[Note]]`
+    pub fn embed_prefix(&self) -> &str {
+        &self.embed_prefix
+    }
+
+    /// Do not prepare the day pages of the weekends
+    pub const fn exclude_weekends(&self) -> bool {
+        self.exclude_weekends
+    }
+
+    /// Days of the weekends, Saturday and Sunday unless configured otherwise
+    pub fn weekend_days(&self) -> &[Weekday] {
+        &self.weekend_days
+    }
+
+    /// Language of the generated text, English unless configured otherwise
+    pub const fn locale(&self) -> Locale {
+        self.locale
+    }
+
+    /// Template of the new pages of the kind, if any
+    pub fn page_template(&self, kind: Period) -> Option<PathBuf> {
+        self.page_templates
+            .get(kind)
+            .map(|file| self.path.join(file))
+    }
+
     pub const fn settings(&self) -> &PageSettings {
         &self.settings
     }
 
     fn read_daily_notes_config(&mut self) -> Result<()> {
-        let daily_notes_config = self.path.join(".obsidian").join("daily-notes.json");
+        let daily_notes_config = self.path.join(DAILY_NOTES_CONFIG);
         if !daily_notes_config.exists() {
             return Ok(());
         }
 
         let config = std::fs::read_to_string(&daily_notes_config)
-            .with_context(|| format!("reading \"{}\"", daily_notes_config.display()))?;
+            .map_err(|e| PrepareError::ReadingFile(e, daily_notes_config.clone()))?;
         let config: Value = serde_json::from_str(&config)
-            .with_context(|| format!("parsing \"{}\"", daily_notes_config.display()))?;
+            .map_err(|e| PrepareError::DailyNotesConfig(e, daily_notes_config.clone()))?;
 
         if let Some(folder) = config["folder"].as_str() {
             log::info!("Using journals folder {folder}");
@@ -124,34 +407,171 @@ impl Config {
         Ok(())
     }
 
-    pub fn read_events(&self) -> Result<Vec<Event>> {
+    /// Most recent modification time of the files the pages are generated from
+    pub fn inputs_modified(&self) -> Result<SystemTime> {
+        let mut inputs_modified = SystemTime::UNIX_EPOCH;
+        let event_files = self.event_files();
+        let inputs = [CONFIG_PAGE, DAILY_NOTES_CONFIG]
+            .into_iter()
+            .chain(event_files.iter().map(String::as_str));
+
+        for input in inputs {
+            let path = self.path.join(input);
+            if !path.exists() {
+                continue;
+            }
+            let modified = std::fs::metadata(&path)
+                .and_then(|metadata| metadata.modified())
+                .map_err(|e| PrepareError::ReadingFile(e, path.clone()))?;
+            inputs_modified = inputs_modified.max(modified);
+        }
+
+        Ok(inputs_modified)
+    }
+
+    /// List the event files, followed by the pages found in the event directories
+    fn event_files(&self) -> Vec<String> {
+        let mut event_files = self.event_files.clone();
+
+        for event_dir in &self.event_dirs {
+            let walker = walkdir::WalkDir::new(self.path.join(event_dir)).sort_by_file_name();
+            for entry in walker {
+                let entry = match entry {
+                    Ok(entry) => entry,
+                    Err(e) => {
+                        log::warn!("Skipping entry of event directory {event_dir:?}: {e}");
+                        continue;
+                    }
+                };
+                let path = entry.path();
+                if !entry.file_type().is_file() || path.extension().is_none_or(|ext| ext != "md") {
+                    continue;
+                }
+                let Ok(relative_path) = path.strip_prefix(&self.path) else {
+                    continue;
+                };
+                let file = relative_path.to_string_lossy().into_owned();
+                if !event_files.contains(&file) {
+                    event_files.push(file);
+                }
+            }
+        }
+
+        event_files
+    }
+
+    /// Check that the configured event files and directories exist, unlike the default event file
+    /// when not configured
+    pub fn check_event_files(&self) -> Result<()> {
+        for file in self.event_files.iter().chain(&self.event_dirs) {
+            if Some(file) == self.implicit_event_file.as_ref() {
+                continue;
+            }
+            if !self.path.join(file).exists() {
+                return Err(PrepareError::EventFileNotFound(file.clone()).into());
+            }
+        }
+        Ok(())
+    }
+
+    /// Read the events of all the event files and directories
+    ///
+    /// Files that cannot be read or contain invalid events are skipped with a warning, unless
+    /// strict where they fail the reading
+    pub fn read_events(&self, strict: bool) -> Result<Vec<Event>> {
+        self.read_events_with_clock(&SystemClock, strict)
+    }
+
+    /// Read the events, the ones valid from "today" starting on the date of the clock
+    pub fn read_events_with_clock<C: Clock>(&self, clock: &C, strict: bool) -> Result<Vec<Event>> {
+        let today = clock.today();
         let mut events = vec![];
-        for event_file in &self.event_files {
+        // The same file can be listed under different paths, e.g. `./events/recurring.md`
+        let mut read_files = std::collections::HashSet::new();
+        for event_file in &self.event_files() {
             let event_page_path = self.path.join(event_file);
-            if !event_page_path.exists() {
+            let Ok(resolved_path) = event_page_path.canonicalize() else {
                 log::info!("Event file not found: {event_file:?}");
                 continue;
+            };
+            if !read_files.insert(resolved_path) {
+                log::debug!("Event file already read: {event_file:?}");
+                continue;
             }
 
-            let event_page = Page::try_from(event_page_path.as_path())?;
-            for entry in event_page.entries() {
-                if let Entry::CodeBlock(block) = entry {
-                    if block.is_toml() {
-                        let event = block.try_into()?;
-                        log::debug!("Event: {event:?}");
-                        events.push(event);
-                    }
-                }
+            match self.read_event_file(event_file, strict) {
+                Ok(file_events) => events.extend(file_events.into_iter().map(|mut event| {
+                    event.add_holidays(&self.holidays);
+                    event.set_weekend_days(&self.weekend_days);
+                    event.set_today(today);
+                    event.set_default_validity(&self.default_validity);
+                    event
+                })),
+                Err(e) if strict => return Err(e),
+                Err(e) => log::warn!("Skipping event file: {e:#}"),
             }
         }
 
         Ok(events)
     }
+
+    /// Read the events of the file, relative to the vault, the errors mentioning the file and the
+    /// index of the invalid block, starting from 1
+    ///
+    /// The invalid blocks are skipped with a warning, keeping the other events of the file, unless
+    /// strict
+    fn read_event_file(&self, event_file: &str, strict: bool) -> Result<Vec<Event>> {
+        let mut events = vec![];
+        let event_page = Page::try_from(self.path.join(event_file))
+            .map_err(PrepareError::from)
+            .with_context(|| event_file.to_owned())?;
+        let blocks = event_page.entries().filter_map(|entry| match entry {
+            Entry::CodeBlock(block) if is_event_block(block, &self.event_block_lang) => Some(block),
+            _ => None,
+        });
+        for (index, block) in blocks.enumerate() {
+            let event = match Event::try_from(block)
+                .map_err(PrepareError::from)
+                .with_context(|| format!("{event_file} block {}", index + 1))
+            {
+                Ok(event) => event,
+                Err(e) if strict => return Err(e),
+                Err(e) => {
+                    log::warn!("Skipping event: {e:#}");
+                    continue;
+                }
+            };
+            log::debug!("Event: {}: {event:?}", event.recurrence().describe());
+            events.push(event);
+        }
+
+        Ok(events)
+    }
 }
 
 impl SerdeConfig {
+    /// Merge the configuration of a later block into this one
+    ///
+    /// The options set in this configuration take precedence, `other` only filling the missing
+    /// ones, while the lists of both are combined, without duplicates.
     fn merge(mut self, other: Self) -> Self {
         let journals_folder = self.journals_folder.or(other.journals_folder);
+        let default_event_file = self.default_event_file.or(other.default_event_file);
+        let default_validity = self.default_validity.or(other.default_validity);
+        let post_hook = self.post_hook.or(other.post_hook);
+        let link_title_style = self.link_title_style.or(other.link_title_style);
+        let week_title = self.week_title.or(other.week_title);
+        let week_label = self.week_label.or(other.week_label);
+        let content_separator = self.content_separator.or(other.content_separator);
+        let layout = self.layout.or(other.layout);
+        let event_block_lang = self.event_block_lang.or(other.event_block_lang);
+        let week_numbering = self.week_numbering.or(other.week_numbering);
+        let frontmatter_delimiter = self.frontmatter_delimiter.or(other.frontmatter_delimiter);
+        let embed_prefix = self.embed_prefix.or(other.embed_prefix);
+        let exclude_weekends = self.exclude_weekends.or(other.exclude_weekends);
+        let weekend_days = self.weekend_days.or(other.weekend_days);
+        let page_templates = self.page_templates.merge(other.page_templates);
+        let locale = self.locale.or(other.locale);
         let settings = PageSettings {
             day: self.settings.day.or(other.settings.day),
             week: self.settings.week.or(other.settings.week),
@@ -164,11 +584,39 @@ impl SerdeConfig {
                 self.event_files.push(file);
             }
         }
+        for dir in other.event_dirs {
+            if !self.event_dirs.contains(&dir) {
+                self.event_dirs.push(dir);
+            }
+        }
+        for holiday in other.holidays {
+            if !self.holidays.contains(&holiday) {
+                self.holidays.push(holiday);
+            }
+        }
 
         Self {
             journals_folder,
             settings,
             event_files: self.event_files,
+            default_event_file,
+            event_dirs: self.event_dirs,
+            holidays: self.holidays,
+            default_validity,
+            post_hook,
+            link_title_style,
+            week_title,
+            week_label,
+            content_separator,
+            layout,
+            event_block_lang,
+            week_numbering,
+            frontmatter_delimiter,
+            embed_prefix,
+            exclude_weekends,
+            weekend_days,
+            page_templates,
+            locale,
         }
     }
 }
@@ -189,6 +637,38 @@ mod tests {
         assert!(config.settings.year.is_none());
     }
 
+    #[test]
+    fn build_with_malformed_preparation_config() -> Result<()> {
+        let temp_dir = assert_fs::TempDir::new()?;
+        temp_dir
+            .child("journal-preparation-config.md")
+            .write_str("```toml\njournals_folder = \n```\n")?;
+
+        let error = Config::new(temp_dir.path().to_path_buf()).unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<PrepareError>(),
+            Some(PrepareError::Config(ConfigError::Toml(_)))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn build_with_malformed_daily_notes_config() -> Result<()> {
+        let temp_dir = assert_fs::TempDir::new()?;
+        temp_dir
+            .child(DAILY_NOTES_CONFIG)
+            .write_str("{\"folder\": ")?;
+
+        let error = Config::new(temp_dir.path().to_path_buf()).unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<PrepareError>(),
+            Some(PrepareError::DailyNotesConfig(_, path)) if path.ends_with(DAILY_NOTES_CONFIG)
+        ));
+
+        Ok(())
+    }
+
     #[test]
     fn build_with_non_existing_path() -> Result<()> {
         let temp_dir = assert_fs::TempDir::new()?;
@@ -232,6 +712,7 @@ mod tests {
             ```toml
             journals_folder = "Foo"
             event_files = ["Hello"]
+            post_hook = "git commit -a"
             [day]
             day_of_week = true
             ```
@@ -240,6 +721,7 @@ mod tests {
         let config = Config::new(temp_dir.path().to_path_buf())?;
 
         assert_eq!(Some("Foo"), config.journals_folder());
+        assert_eq!(Some("git commit -a"), config.post_hook());
         assert_eq!(
             vec!["events/recurring.md".to_owned(), "Hello".to_owned()],
             config.event_files
@@ -296,6 +778,493 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn later_preparation_config_fills_missing_options() -> Result<()> {
+        let temp_dir = assert_fs::TempDir::new()?;
+        let config = temp_dir.child("journal-preparation-config.md");
+
+        config.write_str(indoc! {r#"
+            ```toml
+            event_files = ["Hello"]
+            ```
+
+            ```toml
+            journals_folder = "Bar"
+            default_event_file = false
+            ```
+        "#})?;
+        let config = Config::new(temp_dir.path().to_path_buf())?;
+        assert_eq!(Some("Bar"), config.journals_folder());
+        assert_eq!(vec!["Hello".to_owned()], config.event_files);
+
+        temp_dir
+            .child("journal-preparation-config.md")
+            .write_str(indoc! {r#"
+                ```toml
+                journals_folder = "Foo"
+                default_event_file = "Events.md"
+                ```
+
+                ```toml
+                journals_folder = "Bar"
+                default_event_file = false
+                ```
+
+                ```toml
+                journals_folder = "Baz"
+                ```
+            "#})?;
+        let config = Config::new(temp_dir.path().to_path_buf())?;
+        assert_eq!(Some("Foo"), config.journals_folder());
+        assert_eq!(vec!["Events.md".to_owned()], config.event_files);
+
+        Ok(())
+    }
+
+    #[test]
+    fn default_event_file() -> Result<()> {
+        let temp_dir = assert_fs::TempDir::new()?;
+        let config = temp_dir.child("journal-preparation-config.md");
+
+        config.write_str(indoc! {r"
+            ```toml
+            default_event_file = false
+            ```
+        "})?;
+        let config = Config::new(temp_dir.path().to_path_buf())?;
+        assert!(config.event_files.is_empty());
+
+        temp_dir
+            .child("journal-preparation-config.md")
+            .write_str(indoc! {r#"
+                ```toml
+                default_event_file = "Events.md"
+                event_files = ["Hello"]
+                ```
+            "#})?;
+        let config = Config::new(temp_dir.path().to_path_buf())?;
+        assert_eq!(
+            vec!["Events.md".to_owned(), "Hello".to_owned()],
+            config.event_files
+        );
+
+        temp_dir
+            .child("journal-preparation-config.md")
+            .write_str(indoc! {r"
+                ```toml
+                default_event_file = true
+                ```
+            "})?;
+        let config = Config::new(temp_dir.path().to_path_buf())?;
+        assert_eq!(vec!["events/recurring.md".to_owned()], config.event_files);
+
+        Ok(())
+    }
+
+    #[test]
+    fn event_dirs() -> Result<()> {
+        let temp_dir = assert_fs::TempDir::new()?;
+        temp_dir
+            .child("journal-preparation-config.md")
+            .write_str(indoc! {r#"
+                ```toml
+                event_files = ["events/work/meetings.md"]
+                event_dirs = ["events"]
+                ```
+            "#})?;
+        let event = |content: &str| {
+            format!("```toml\nfrequency = \"daily\"\ncontent = \"{content}\"\n```\n")
+        };
+        temp_dir
+            .child("events/recurring.md")
+            .write_str(&event("Recurring"))?;
+        temp_dir
+            .child("events/work/meetings.md")
+            .write_str(&event("Meetings"))?;
+        temp_dir
+            .child("events/family/birthdays.md")
+            .write_str(&event("Birthdays"))?;
+        temp_dir
+            .child("events/family/notes.txt")
+            .write_str(&event("Notes"))?;
+
+        let config = Config::new(temp_dir.path().to_path_buf())?;
+        assert_eq!(
+            vec![
+                "events/recurring.md".to_owned(),
+                "events/work/meetings.md".to_owned(),
+                "events/family/birthdays.md".to_owned(),
+            ],
+            config.event_files()
+        );
+
+        let events = config.read_events(false)?;
+        assert_eq!(3, events.len());
+        assert_eq!(vec!["Birthdays".to_owned()], events[2].content);
+
+        Ok(())
+    }
+
+    #[test]
+    fn event_block_lang() -> Result<()> {
+        let temp_dir = assert_fs::TempDir::new()?;
+        temp_dir.child("events/recurring.md").write_str(indoc! {r#"
+                ```yaml
+                frequency: daily
+                content: Yaml
+                ```
+
+                ```toml
+                frequency = "daily"
+                content = "Toml"
+                ```
+            "#})?;
+
+        let config = Config::new(temp_dir.path().to_path_buf())?;
+        let events = config.read_events(false)?;
+        assert_eq!(1, events.len());
+        assert_eq!(vec!["Toml".to_owned()], events[0].content);
+
+        temp_dir
+            .child("journal-preparation-config.md")
+            .write_str(indoc! {r#"
+                ```toml
+                event_block_lang = "yaml"
+                ```
+            "#})?;
+
+        let config = Config::new(temp_dir.path().to_path_buf())?;
+        let events = config.read_events(false)?;
+        assert_eq!(1, events.len());
+        assert_eq!(vec!["Yaml".to_owned()], events[0].content);
+
+        // `yml` and `yaml` are the same language
+        temp_dir.child("events/recurring.md").write_str(indoc! {r"
+                ```yml
+                frequency: daily
+                content: Yml
+                ```
+            "})?;
+        let events = config.read_events(false)?;
+        assert_eq!(1, events.len());
+        assert_eq!(vec!["Yml".to_owned()], events[0].content);
+
+        Ok(())
+    }
+
+    #[test]
+    fn events_from_today() -> Result<()> {
+        use utils::clock::FixedClock;
+
+        let temp_dir = assert_fs::TempDir::new()?;
+        temp_dir.child("events/recurring.md").write_str(indoc! {r#"
+                ```toml
+                frequency = "daily"
+                content = "Stretching"
+                from = "today"
+                ```
+            "#})?;
+
+        let today = NaiveDate::from_ymd_opt(2025, 3, 10).unwrap();
+        let config = Config::new(temp_dir.path().to_path_buf())?;
+        let events = config.read_events_with_clock(&FixedClock(today), false)?;
+        assert_eq!(1, events.len());
+        assert!(!events[0].matches(today.pred_opt().unwrap()));
+        assert!(events[0].matches(today));
+
+        Ok(())
+    }
+
+    #[test]
+    fn holidays() -> Result<()> {
+        let temp_dir = assert_fs::TempDir::new()?;
+        temp_dir
+            .child("journal-preparation-config.md")
+            .write_str(indoc! {r#"
+                ```toml
+                holidays = ["2025-12-25"]
+                ```
+
+                ```toml
+                holidays = ["2025-12-25", "2026-01-01"]
+                ```
+            "#})?;
+        temp_dir.child("events/recurring.md").write_str(indoc! {r#"
+                ```toml
+                frequency = "daily"
+                content = "Work"
+                except_holidays = true
+                ```
+
+                ```toml
+                frequency = "daily"
+                content = "Walk"
+                ```
+            "#})?;
+
+        let config = Config::new(temp_dir.path().to_path_buf())?;
+        let events = config.read_events(false)?;
+        let christmas = NaiveDate::from_ymd_opt(2025, 12, 25).unwrap();
+        let new_year = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+        let day_after = NaiveDate::from_ymd_opt(2026, 1, 2).unwrap();
+
+        assert_eq!(2, config.holidays.len());
+        assert!(!events[0].matches(christmas));
+        assert!(!events[0].matches(new_year));
+        assert!(events[0].matches(day_after));
+        assert!(events[1].matches(christmas));
+
+        Ok(())
+    }
+
+    #[test]
+    fn default_validity() -> Result<()> {
+        let temp_dir = assert_fs::TempDir::new()?;
+        temp_dir
+            .child("journal-preparation-config.md")
+            .write_str(indoc! {r#"
+                ```toml
+                default_validity = { from = "2025-01-01", to = "2025-12-31" }
+                ```
+            "#})?;
+        temp_dir.child("events/recurring.md").write_str(indoc! {r#"
+                ```toml
+                frequency = "daily"
+                content = "Default"
+                ```
+
+                ```toml
+                frequency = "daily"
+                content = "Own"
+                from = "2024-06-01"
+                to = "2024-06-30"
+                ```
+
+                ```toml
+                frequency = "daily"
+                content = "Until"
+                to = "2025-03-31"
+                ```
+            "#})?;
+
+        let config = Config::new(temp_dir.path().to_path_buf())?;
+        let events = config.read_events(false)?;
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+        assert!(!events[0].matches(date(2024, 12, 31)));
+        assert!(events[0].matches(date(2025, 1, 1)));
+        assert!(events[0].matches(date(2025, 12, 31)));
+        assert!(!events[0].matches(date(2026, 1, 1)));
+
+        assert!(events[1].matches(date(2024, 6, 15)));
+        assert!(!events[1].matches(date(2025, 6, 15)));
+
+        // Only the bound set by the event applies
+        assert!(events[2].matches(date(2024, 12, 31)));
+        assert!(events[2].matches(date(2025, 3, 31)));
+        assert!(!events[2].matches(date(2025, 4, 1)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn init() -> Result<()> {
+        let temp_dir = assert_fs::TempDir::new()?;
+        Config::init(temp_dir.path())?;
+
+        let config = Config::new(temp_dir.path().to_path_buf())?;
+        assert!(config.settings.day.is_some());
+        assert!(config.settings.week.is_some());
+        assert!(config.settings.month.is_some());
+        assert!(config.settings.year.is_some());
+        assert_eq!(1, config.read_events(false)?.len());
+
+        let existing = "My own config\n";
+        let config_page = temp_dir.child("journal-preparation-config.md");
+        config_page.write_str(existing)?;
+        Config::init(temp_dir.path())?;
+        config_page.assert(existing);
+
+        Ok(())
+    }
+
+    #[test]
+    fn read_events_once_per_file() -> Result<()> {
+        let temp_dir = assert_fs::TempDir::new()?;
+        temp_dir
+            .child("journal-preparation-config.md")
+            .write_str(indoc! {r#"
+                ```toml
+                event_files = ["events/shared.md"]
+                ```
+
+                ```toml
+                event_files = ["./events/shared.md", "events/../events/shared.md"]
+                event_dirs = ["events"]
+                ```
+            "#})?;
+        temp_dir.child("events/shared.md").write_str(indoc! {r#"
+                ```toml
+                frequency = "daily"
+                content = "Shared"
+                ```
+            "#})?;
+
+        let config = Config::new(temp_dir.path().to_path_buf())?;
+        assert_eq!(
+            vec![
+                "events/recurring.md".to_owned(),
+                "events/shared.md".to_owned(),
+                "./events/shared.md".to_owned(),
+                "events/../events/shared.md".to_owned(),
+            ],
+            config.event_files()
+        );
+
+        let events = config.read_events(false)?;
+        assert_eq!(1, events.len());
+        assert_eq!(vec!["Shared".to_owned()], events[0].content);
+
+        Ok(())
+    }
+
+    #[test]
+    fn read_events_skips_invalid_files() -> Result<()> {
+        let temp_dir = assert_fs::TempDir::new()?;
+        temp_dir
+            .child("journal-preparation-config.md")
+            .write_str(indoc! {r#"
+                ```toml
+                event_files = ["events/invalid.md", "events/not-utf8.md"]
+                ```
+            "#})?;
+        temp_dir.child("events/recurring.md").write_str(indoc! {r#"
+                ```toml
+                frequency = "daily"
+                content = "Foo"
+                ```
+            "#})?;
+        temp_dir.child("events/invalid.md").write_str(indoc! {r#"
+                ```toml
+                frequency = "weekly"
+                content = "Foo"
+                ```
+            "#})?;
+        temp_dir
+            .child("events/not-utf8.md")
+            .write_binary(b"\xff\xfe")?;
+
+        let config = Config::new(temp_dir.path().to_path_buf())?;
+        let events = config.read_events(false)?;
+        assert_eq!(1, events.len());
+        assert_eq!(vec!["Foo".to_owned()], events[0].content);
+
+        let error = config.read_events(true).unwrap_err();
+        assert_eq!("events/invalid.md block 1", error.to_string());
+
+        Ok(())
+    }
+
+    #[test]
+    fn read_events_skips_invalid_blocks() -> Result<()> {
+        let temp_dir = assert_fs::TempDir::new()?;
+        temp_dir.child("events/recurring.md").write_str(indoc! {r#"
+                ```toml
+                frequency = "daily"
+                content = "Foo"
+                ```
+
+                ```toml
+                frequency = "weekly"
+                content = "Invalid"
+                ```
+
+                ```toml
+                frequency = "daily"
+                content = "Bar"
+                ```
+            "#})?;
+
+        let config = Config::new(temp_dir.path().to_path_buf())?;
+        let events = config.read_events(false)?;
+        assert_eq!(2, events.len());
+        assert_eq!(vec!["Foo".to_owned()], events[0].content);
+        assert_eq!(vec!["Bar".to_owned()], events[1].content);
+
+        let error = config.read_events(true).unwrap_err();
+        assert_eq!("events/recurring.md block 2", error.to_string());
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_event_files() -> Result<()> {
+        let temp_dir = assert_fs::TempDir::new()?;
+        let config = temp_dir.child("journal-preparation-config.md");
+
+        Config::new(temp_dir.path().to_path_buf())?.check_event_files()?;
+
+        config.write_str("```toml\ndefault_event_file = \"events/recurring.md\"\n```\n")?;
+        let error = Config::new(temp_dir.path().to_path_buf())?
+            .check_event_files()
+            .unwrap_err();
+        assert_eq!(
+            "Event file not found: \"events/recurring.md\"",
+            error.to_string()
+        );
+
+        temp_dir.child("events/recurring.md").touch()?;
+        config.write_str("```toml\nevent_dirs = [\"events/work\"]\n```\n")?;
+        let error = Config::new(temp_dir.path().to_path_buf())?
+            .check_event_files()
+            .unwrap_err();
+        assert_eq!("Event file not found: \"events/work\"", error.to_string());
+
+        temp_dir.child("events/work").create_dir_all()?;
+        Config::new(temp_dir.path().to_path_buf())?.check_event_files()?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn read_event_file_error_context() -> Result<()> {
+        let temp_dir = assert_fs::TempDir::new()?;
+        temp_dir.child("events/work.md").write_str(indoc! {r#"
+                ```toml
+                frequency = "daily"
+                content = "Foo"
+                ```
+                ```json
+                {"frequency": "weekly"}
+                ```
+                ```toml
+                frequency = "sometimes"
+                content = "Bar"
+                ```
+            "#})?;
+
+        let config = Config::new(temp_dir.path().to_path_buf())?;
+        let error = config.read_event_file("events/work.md", true).unwrap_err();
+        assert!(
+            format!("{error:#}").starts_with("events/work.md block 2: "),
+            "{error:#}"
+        );
+        assert!(error.downcast_ref::<PrepareError>().is_some());
+
+        temp_dir
+            .child("events/not-utf8.md")
+            .write_binary(b"\xff\xfe")?;
+        let error = config
+            .read_event_file("events/not-utf8.md", false)
+            .unwrap_err();
+        assert!(
+            format!("{error:#}").starts_with("events/not-utf8.md: "),
+            "{error:#}"
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn daily_notes_folder() -> Result<()> {
         let temp_dir = assert_fs::TempDir::new()?;
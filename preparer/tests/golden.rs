@@ -0,0 +1,85 @@
+//! Run the preparer over the fixture vaults in `tests/golden/<scenario>/input` and compare the
+//! resulting vault with `tests/golden/<scenario>/expected`
+//!
+//! Set `UPDATE_GOLDEN` to write the resulting vault as the new expected output.
+
+use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+fn golden_dir(scenario: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(scenario)
+}
+
+/// Content of all the files of the directory, by path relative to the directory
+fn read_files(dir: &Path) -> Result<BTreeMap<PathBuf, String>> {
+    let mut files = BTreeMap::new();
+    for entry in walkdir::WalkDir::new(dir) {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let content = std::fs::read_to_string(entry.path())
+            .with_context(|| format!("reading {}", entry.path().display()))?;
+        files.insert(entry.path().strip_prefix(dir)?.to_path_buf(), content);
+    }
+    Ok(files)
+}
+
+fn write_files(dir: &Path, files: &BTreeMap<PathBuf, String>) -> Result<()> {
+    if dir.exists() {
+        std::fs::remove_dir_all(dir)?;
+    }
+    for (path, content) in files {
+        let path = dir.join(path);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, content)?;
+    }
+    Ok(())
+}
+
+fn run_scenario(scenario: &str) -> Result<()> {
+    let dir = golden_dir(scenario);
+    let args = std::fs::read_to_string(dir.join("args"))?;
+    let vault = assert_fs::TempDir::new()?;
+    write_files(vault.path(), &read_files(&dir.join("input"))?)?;
+
+    assert_cmd::cargo::cargo_bin_cmd!("preparer")
+        .arg("--path")
+        .arg(vault.path())
+        .args(args.split_whitespace())
+        .assert()
+        .success();
+
+    let actual = read_files(vault.path())?;
+    let expected_dir = dir.join("expected");
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        return write_files(&expected_dir, &actual);
+    }
+    let expected = read_files(&expected_dir)?;
+
+    assert_eq!(
+        expected.keys().collect::<Vec<_>>(),
+        actual.keys().collect::<Vec<_>>(),
+        "files of scenario {scenario}"
+    );
+    for (path, content) in &expected {
+        assert_eq!(
+            content,
+            &actual[path],
+            "content of {} in scenario {scenario}",
+            path.display()
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn basic() -> Result<()> {
+    run_scenario("basic")
+}
@@ -1,6 +1,8 @@
 use anyhow::Result;
 use assert_cmd::Command;
+use assert_fs::prelude::*;
 use assert_fs::TempDir;
+use indoc::indoc;
 use predicates::str;
 
 pub struct Env {
@@ -32,3 +34,1428 @@ fn empty() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn json_logs() -> Result<()> {
+    let env = Env::new()?;
+
+    let output = env
+        .command()?
+        .args(["--from", "2025-01-15", "--to", "2025-01-15"])
+        .args(["--log-format", "json", "-vv"])
+        .output()?;
+    assert!(output.status.success());
+
+    let stderr = String::from_utf8(output.stderr)?;
+    assert!(!stderr.is_empty());
+    for line in stderr.lines() {
+        let log: serde_json::Value = serde_json::from_str(line)?;
+        assert!(log["level"].is_string());
+        assert!(log["target"].is_string());
+        assert!(log["message"].is_string());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn skip_existing() -> Result<()> {
+    let env = Env::new()?;
+    let content = "---\nfoo: bar\n---\nMy own notes\n";
+    let day = env.path.child("2025-01-15.md");
+    day.write_str(content)?;
+
+    env.command()?
+        .args([
+            "--from",
+            "2025-01-15",
+            "--to",
+            "2025-01-15",
+            "--skip-existing",
+        ])
+        .assert()
+        .success()
+        .stderr(str::is_empty());
+
+    day.assert(content);
+    assert!(env.path.child("2025/Week 03.md").path().exists());
+
+    Ok(())
+}
+
+#[test]
+fn month_and_year_events() -> Result<()> {
+    let env = Env::new()?;
+    env.path.child("events/recurring.md").write_str(indoc! {r#"
+        ```toml
+        frequency = "yearly"
+        yeardays = [74]
+        content = "Birthday"
+        ```
+    "#})?;
+
+    env.command()?
+        .args(["--from", "2025-03-01", "--to", "2025-04-30"])
+        .args(["--month", "events", "--year", "events", "--no-day-page"])
+        .assert()
+        .success()
+        .stderr(str::is_empty());
+
+    env.path.child("2025/March.md").assert(indoc! {"
+        #### Events
+        - [[/2025-03-15|2025-03-15]] Birthday
+    "});
+    assert!(!env.path.child("2025/April.md").path().exists());
+    env.path.child("2025.md").assert(indoc! {"
+        #### [[/2025/March|March]]
+        - [[/2025-03-15|2025-03-15]] Birthday
+    "});
+
+    Ok(())
+}
+
+#[test]
+fn month_and_year_events_headings() -> Result<()> {
+    let env = Env::new()?;
+    env.path
+        .child("journal-preparation-config.md")
+        .write_str(indoc! {r#"
+            ```toml
+            locale = "french"
+
+            [month]
+            events = true
+            week_heading_level = 2
+
+            [year]
+            events = true
+            month_heading_level = 3
+            ```
+        "#})?;
+    env.path.child("events/recurring.md").write_str(indoc! {r#"
+        ```toml
+        frequency = "yearly"
+        yeardays = [74]
+        content = "Birthday"
+        ```
+    "#})?;
+
+    env.command()?
+        .args([
+            "--from",
+            "2025-03-01",
+            "--to",
+            "2025-03-31",
+            "--no-day-page",
+        ])
+        .assert()
+        .success()
+        .stderr(str::is_empty());
+
+    env.path.child("2025/March.md").assert(indoc! {"
+        ## Événements
+        - [[/2025-03-15|2025-03-15]] Birthday
+    "});
+    env.path.child("2025.md").assert(indoc! {"
+        ### [[/2025/March|March]]
+        - [[/2025-03-15|2025-03-15]] Birthday
+    "});
+
+    Ok(())
+}
+
+#[test]
+fn event_embed() -> Result<()> {
+    let env = Env::new()?;
+    env.path.child("events/recurring.md").write_str(indoc! {r#"
+        ```toml
+        frequency = "weekly"
+        weekdays = ["Wednesday"]
+        content = "- [ ] Review the week"
+        embed = "Weekly review"
+        ```
+    "#})?;
+
+    env.command()?
+        .args(["--from", "2025-01-15", "--to", "2025-01-15"])
+        .args(["--day", "events", "--month", "events"])
+        .args(["--no-week-page", "--no-year-page"])
+        .assert()
+        .success()
+        .stderr(str::is_empty());
+
+    env.path.child("2025-01-15.md").assert(indoc! {"
+        - [ ] Review the week
+        ![[Weekly review]]
+    "});
+    env.path.child("2025/January.md").assert(str::contains(
+        "- [[/2025-01-15|2025-01-15]] [[Weekly review]]\n",
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn event_content_lines() -> Result<()> {
+    let env = Env::new()?;
+    env.path.child("events/recurring.md").write_str(indoc! {r#"
+        ```toml
+        frequency = "daily"
+        content = ["- [ ] Stretching", "- [ ] Reading"]
+        ```
+    "#})?;
+
+    env.command()?
+        .args(["--from", "2025-01-15", "--to", "2025-01-15"])
+        .args(["--day", "events"])
+        .assert()
+        .success()
+        .stderr(str::is_empty());
+
+    env.path.child("2025-01-15.md").assert(indoc! {"
+        - [ ] Stretching
+        - [ ] Reading
+    "});
+
+    Ok(())
+}
+
+#[test]
+fn event_callout() -> Result<()> {
+    let env = Env::new()?;
+    env.path.child("events/recurring.md").write_str(indoc! {r#"
+        ```toml
+        frequency = "daily"
+        content = ["- [ ] Stretching", "", "- [ ] Reading"]
+        ```
+        ```toml
+        frequency = "weekly"
+        weekdays = ["Thursday"]
+        embed = "Weekly review"
+        ```
+    "#})?;
+
+    // The callout is left as is by the second run
+    for _ in 0..2 {
+        env.command()?
+            .args(["--from", "2025-01-15", "--to", "2025-01-16"])
+            .args(["--day", "events,callout"])
+            .assert()
+            .success()
+            .stderr(str::is_empty());
+
+        env.path.child("2025-01-15.md").assert(indoc! {"
+            <!-- prep:start id=events -->
+            > [!note]- Events
+            > - [ ] Stretching
+            >
+            > - [ ] Reading
+            <!-- prep:end -->
+        "});
+        env.path.child("2025-01-16.md").assert(indoc! {"
+            <!-- prep:start id=events -->
+            > [!note]- Events
+            > - [ ] Stretching
+            >
+            > - [ ] Reading
+            > ![[Weekly review]]
+            <!-- prep:end -->
+        "});
+    }
+
+    env.path
+        .child("journal-preparation-config.md")
+        .write_str(indoc! {r#"
+            ```toml
+            [day]
+            events = true
+            callout = true
+            callout_type = "todo"
+            callout_title = "Routine"
+            ```
+        "#})?;
+    env.command()?
+        .args(["--from", "2025-01-17", "--to", "2025-01-17"])
+        .assert()
+        .success()
+        .stderr(str::is_empty());
+
+    env.path.child("2025-01-17.md").assert(indoc! {"
+        <!-- prep:start id=events -->
+        > [!todo]- Routine
+        > - [ ] Stretching
+        >
+        > - [ ] Reading
+        <!-- prep:end -->
+    "});
+
+    Ok(())
+}
+
+#[test]
+fn event_markers() -> Result<()> {
+    let env = Env::new()?;
+    let events = env.path.child("events/recurring.md");
+    let day = env.path.child("2025-01-15.md");
+    let run = || -> Result<()> {
+        env.command()?
+            .args(["--from", "2025-01-15", "--to", "2025-01-15"])
+            .args(["--day", "events,markers"])
+            .assert()
+            .success()
+            .stderr(str::is_empty());
+        Ok(())
+    };
+
+    events.write_str(indoc! {r#"
+        ```toml
+        frequency = "daily"
+        content = ["- [ ] Stretching", "- [ ] Reading"]
+        ```
+    "#})?;
+    run()?;
+    day.assert(indoc! {"
+        <!-- prep:start id=events -->
+        - [ ] Stretching
+        - [ ] Reading
+        <!-- prep:end -->
+    "});
+
+    day.write_str(&(std::fs::read_to_string(day.path())? + "My notes\n"))?;
+    events.write_str(indoc! {r#"
+        ```toml
+        frequency = "daily"
+        content = ["- [ ] Yoga", "- [ ] Reading"]
+        ```
+    "#})?;
+    run()?;
+    day.assert(indoc! {"
+        <!-- prep:start id=events -->
+        - [ ] Yoga
+        - [ ] Reading
+        <!-- prep:end -->
+        My notes
+    "});
+
+    Ok(())
+}
+
+#[test]
+fn compact_year() -> Result<()> {
+    let env = Env::new()?;
+
+    env.command()?
+        .args(["--from", "2025-01-15", "--to", "2025-01-15"])
+        .args(["--year", "month,compact", "--no-day-page"])
+        .args(["--no-week-page", "--no-month-page"])
+        .assert()
+        .success()
+        .stderr(str::is_empty());
+
+    env.path.child("2025.md").assert(concat!(
+        "[[/2025/January|Jan]] · [[/2025/February|Feb]] · [[/2025/March|Mar]] · ",
+        "[[/2025/April|Apr]] · [[/2025/May|May]] · [[/2025/June|Jun]] · ",
+        "[[/2025/July|Jul]] · [[/2025/August|Aug]] · [[/2025/September|Sep]] · ",
+        "[[/2025/October|Oct]] · [[/2025/November|Nov]] · [[/2025/December|Dec]]\n",
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn year_weeks() -> Result<()> {
+    let env = Env::new()?;
+    let run = |year: &str| -> Result<Vec<String>> {
+        env.command()?
+            .args(["--from", year, "--to", year])
+            .args(["--year", "weeks", "--no-day-page"])
+            .args(["--no-week-page", "--no-month-page"])
+            .assert()
+            .success()
+            .stderr(str::is_empty());
+
+        let content = std::fs::read_to_string(env.path.child(format!("{year}.md")).path())?;
+        Ok(content.lines().map(str::to_owned).collect())
+    };
+
+    // 2020 starts on a Wednesday, so its first ISO week starts on 2019-12-30
+    let weeks = run("2020")?;
+    assert_eq!(53, weeks.len());
+    assert_eq!("[[/2020/Week 01|Week 01]]", weeks[0]);
+    assert_eq!("[[/2020/Week 53|Week 53]]", weeks[52]);
+
+    // 2021 starts on a Friday, its first days belong to the week 53 of 2020
+    let weeks = run("2021")?;
+    assert_eq!(52, weeks.len());
+    assert_eq!("[[/2021/Week 01|Week 01]]", weeks[0]);
+    assert_eq!("[[/2021/Week 52|Week 52]]", weeks[51]);
+
+    Ok(())
+}
+
+#[test]
+fn month_days() -> Result<()> {
+    let env = Env::new()?;
+    let run = |month: &str| -> Result<String> {
+        env.command()?
+            .args(["--from", "2025-02-01", "--to", "2025-02-01"])
+            .args(["--month", month, "--no-day-page"])
+            .args(["--no-week-page", "--no-year-page"])
+            .assert()
+            .success()
+            .stderr(str::is_empty());
+
+        let path = env.path.child("2025/February.md");
+        let content = std::fs::read_to_string(path.path())?;
+        std::fs::remove_file(path.path())?;
+        Ok(content)
+    };
+
+    let embedded = run("month")?;
+    assert!(embedded.starts_with(indoc! {"
+        #### [[/2025/Week 05|Week 05]]
+        - Saturday ![[/2025-02-01|2025-02-01]]
+        - Sunday ![[/2025-02-02|2025-02-02]]
+        #### [[/2025/Week 06|Week 06]]
+        - Monday ![[/2025-02-03|2025-02-03]]
+    "}));
+
+    let linked = run("month,links")?;
+    assert!(linked.starts_with(indoc! {"
+        #### [[/2025/Week 05|Week 05]]
+        - Saturday [[/2025-02-01|2025-02-01]]
+        - Sunday [[/2025-02-02|2025-02-02]]
+        #### [[/2025/Week 06|Week 06]]
+        - Monday [[/2025-02-03|2025-02-03]]
+    "}));
+    assert_eq!(embedded.replace('!', ""), linked);
+
+    Ok(())
+}
+
+#[test]
+fn month_weeks() -> Result<()> {
+    let env = Env::new()?;
+
+    env.command()?
+        .args(["--from", "2026-02-01", "--to", "2026-02-01"])
+        .args(["--month", "weeks", "--no-day-page"])
+        .args(["--no-week-page", "--no-year-page"])
+        .assert()
+        .success()
+        .stderr(str::is_empty());
+
+    env.path.child("2026/February.md").assert(indoc! {"
+        ![[/2026/Week 05|Week 05]]
+        ![[/2026/Week 06|Week 06]]
+        ![[/2026/Week 07|Week 07]]
+        ![[/2026/Week 08|Week 08]]
+        ![[/2026/Week 09|Week 09]]
+    "});
+
+    Ok(())
+}
+
+#[test]
+fn month_days_in_range() -> Result<()> {
+    let env = Env::new()?;
+
+    let run = |from: &str, to: &str| {
+        env.command()
+            .unwrap()
+            .args(["--from", from, "--to", to])
+            .args(["--month", "month,range", "--no-day-page"])
+            .args(["--no-week-page", "--no-year-page"])
+            .assert()
+            .success()
+            .stderr(str::is_empty());
+    };
+
+    run("2025-02-10", "2025-02-11");
+    env.path.child("2025/February.md").assert(indoc! {"
+        <!-- prep:start id=month -->
+        #### [[/2025/Week 07|Week 07]]
+        - Monday ![[/2025-02-10|2025-02-10]]
+        - Tuesday ![[/2025-02-11|2025-02-11]]
+        <!-- prep:end -->
+    "});
+
+    run("2025-02-07", "2025-02-12");
+    env.path.child("2025/February.md").assert(indoc! {"
+        <!-- prep:start id=month -->
+        #### [[/2025/Week 06|Week 06]]
+        - Friday ![[/2025-02-07|2025-02-07]]
+        - Saturday ![[/2025-02-08|2025-02-08]]
+        - Sunday ![[/2025-02-09|2025-02-09]]
+        #### [[/2025/Week 07|Week 07]]
+        - Monday ![[/2025-02-10|2025-02-10]]
+        - Tuesday ![[/2025-02-11|2025-02-11]]
+        - Wednesday ![[/2025-02-12|2025-02-12]]
+        <!-- prep:end -->
+    "});
+
+    Ok(())
+}
+
+#[test]
+fn month_day_label() -> Result<()> {
+    let env = Env::new()?;
+    let run = |label: &str| -> Result<String> {
+        env.path
+            .child("journal-preparation-config.md")
+            .write_str(&format!(
+                "```toml\n[month]\nmonth = true\nday_label = \"{label}\"\n```\n"
+            ))?;
+        env.command()?
+            .args(["--from", "2025-02-01", "--to", "2025-02-01"])
+            .args(["--no-day-page"])
+            .args(["--no-week-page", "--no-year-page"])
+            .assert()
+            .success()
+            .stderr(str::is_empty());
+
+        let path = env.path.child("2025/February.md");
+        let content = std::fs::read_to_string(path.path())?;
+        std::fs::remove_file(path.path())?;
+        Ok(content)
+    };
+
+    assert!(run("full")?.starts_with(indoc! {"
+        #### [[/2025/Week 05|Week 05]]
+        - Saturday ![[/2025-02-01|2025-02-01]]
+        - Sunday ![[/2025-02-02|2025-02-02]]
+        #### [[/2025/Week 06|Week 06]]
+        - Monday ![[/2025-02-03|2025-02-03]]
+    "}));
+    assert!(run("abbrev")?.starts_with(indoc! {"
+        #### [[/2025/Week 05|Week 05]]
+        - Sat ![[/2025-02-01|2025-02-01]]
+        - Sun ![[/2025-02-02|2025-02-02]]
+        #### [[/2025/Week 06|Week 06]]
+        - Mon ![[/2025-02-03|2025-02-03]]
+    "}));
+    assert!(run("none")?.starts_with(indoc! {"
+        #### [[/2025/Week 05|Week 05]]
+        - ![[/2025-02-01|2025-02-01]]
+        - ![[/2025-02-02|2025-02-02]]
+        #### [[/2025/Week 06|Week 06]]
+        - ![[/2025-02-03|2025-02-03]]
+    "}));
+
+    Ok(())
+}
+
+#[test]
+fn stats() -> Result<()> {
+    let env = Env::new()?;
+    env.path.child("events/recurring.md").write_str(indoc! {r#"
+            ```toml
+            frequency = "weekly"
+            weekdays = ["Monday", "Thursday"]
+            content = "Gym"
+            ```
+            ```toml
+            frequency = "monthly"
+            monthdays = [2, 15]
+            content = "Invoices"
+            ```
+            ```toml
+            frequency = "once"
+            dates = ["2025-06-02", "2025-07-01"]
+            content = "Dentist"
+            ```
+            ```toml
+            frequency = "yearly"
+            yeardays = [1]
+            content = "New year"
+            ```
+        "#})?;
+
+    env.command()?
+        .args(["--from", "2025-06", "--stats"])
+        .assert()
+        .success()
+        .stderr(str::is_empty())
+        .stdout(indoc! {"
+            Events from 2025-06-01 to 2025-06-30
+            Days with events: 10/30
+            Busiest day: 2025-06-02 (3 events)
+            Events per frequency:
+            - weekly: 1
+            - monthly: 1
+            - once: 1
+        "});
+
+    env.path
+        .child("2025-06-01.md")
+        .assert(predicates::path::missing());
+    env.path
+        .child("2025/June.md")
+        .assert(predicates::path::missing());
+
+    Ok(())
+}
+
+#[test]
+fn post_hook() -> Result<()> {
+    let env = Env::new()?;
+
+    env.command()?
+        .args(["--from", "2025-01-15", "--to", "2025-01-15"])
+        .args(["--post-hook", "echo $PREPARER_CHANGED_FILES > hook-ran"])
+        .assert()
+        .success()
+        .stderr(str::is_empty());
+
+    env.path.child("hook-ran").assert("4\n");
+
+    Ok(())
+}
+
+#[test]
+fn week_number() -> Result<()> {
+    let env = Env::new()?;
+
+    env.command()?
+        .args(["--from", "2020-12-31", "--to", "2021-01-04"])
+        .args(["--day", "week-number", "--no-week-page"])
+        .args(["--no-month-page", "--no-year-page"])
+        .assert()
+        .success()
+        .stderr(str::is_empty());
+
+    env.path
+        .child("2020-12-31.md")
+        .assert("---\nweek_number: 53\n---\n");
+    env.path
+        .child("2021-01-03.md")
+        .assert("---\nweek_number: 53\n---\n");
+    env.path
+        .child("2021-01-04.md")
+        .assert("---\nweek_number: 1\n---\n");
+
+    Ok(())
+}
+
+#[test]
+fn day_of_year() -> Result<()> {
+    let env = Env::new()?;
+    let run = |from: &str, to: &str| -> Result<()> {
+        env.command()?
+            .args(["--from", from, "--to", to])
+            .args(["--day", "day-of-year,week-number", "--no-week-page"])
+            .args(["--no-month-page", "--no-year-page"])
+            .assert()
+            .success()
+            .stderr(str::is_empty());
+        Ok(())
+    };
+
+    run("2024-01-01", "2024-01-01")?;
+    run("2024-12-31", "2024-12-31")?;
+    run("2025-12-31", "2025-12-31")?;
+
+    env.path
+        .child("2024-01-01.md")
+        .assert("---\nday_of_year: 1\nweek_number: 1\n---\n");
+    env.path
+        .child("2024-12-31.md")
+        .assert("---\nday_of_year: 366\nweek_number: 1\n---\n");
+    env.path
+        .child("2025-12-31.md")
+        .assert("---\nday_of_year: 365\nweek_number: 1\n---\n");
+
+    Ok(())
+}
+
+#[test]
+fn aliases() -> Result<()> {
+    let env = Env::new()?;
+    let run = |args: &[&str]| -> Result<()> {
+        env.command()?
+            .args(["--from", "2025-01-06", "--to", "2025-01-06"])
+            .args(args)
+            .args(["--no-week-page", "--no-month-page", "--no-year-page"])
+            .assert()
+            .success()
+            .stderr(str::is_empty());
+        Ok(())
+    };
+
+    run(&["--day", "aliases"])?;
+    env.path.child("2025-01-06.md").assert(indoc! {"
+        ---
+        aliases:
+          - \"Monday, 6 January 2025\"
+        ---
+    "});
+
+    env.path
+        .child("journal-preparation-config.md")
+        .write_str(indoc! {r#"
+            ```toml
+            [day]
+            aliases = true
+            alias_format = "%d/%m/%Y"
+            ```
+        "#})?;
+    run(&[])?;
+    env.path.child("2025-01-06.md").assert(indoc! {"
+        ---
+        aliases:
+          - \"Monday, 6 January 2025\"
+          - 06/01/2025
+        ---
+    "});
+
+    Ok(())
+}
+
+#[test]
+fn locale() -> Result<()> {
+    let env = Env::new()?;
+    env.path
+        .child("journal-preparation-config.md")
+        .write_str("```toml\nlocale = \"french\"\n```\n")?;
+
+    env.command()?
+        .args(["--from", "2025-01-06", "--to", "2025-01-06"])
+        .args(["--day", "day,week,aliases", "--week", "week"])
+        .args(["--no-month-page", "--no-year-page"])
+        .assert()
+        .success()
+        .stderr(str::is_empty());
+
+    env.path.child("2025-01-06.md").assert(indoc! {"
+        ---
+        day: lundi
+        week: \"[[/2025/Semaine 02|Semaine 02]]\"
+        aliases:
+          - lundi 6 janvier 2025
+        ---
+    "});
+    assert!(
+        std::fs::read_to_string(env.path.child("2025/Semaine 02.md").path())?
+            .starts_with("- lundi ![[/2025-01-06|2025-01-06]]\n")
+    );
+
+    Ok(())
+}
+
+#[test]
+fn nav_title() -> Result<()> {
+    let env = Env::new()?;
+    let run = || -> Result<()> {
+        env.command()?
+            .args(["--from", "2025-01-06", "--to", "2025-01-06"])
+            .args(["--no-week-page", "--no-month-page", "--no-year-page"])
+            .assert()
+            .success()
+            .stderr(str::is_empty());
+        Ok(())
+    };
+
+    env.path
+        .child("journal-preparation-config.md")
+        .write_str(indoc! {r"
+            ```toml
+            [day]
+            nav_link = true
+            ```
+        "})?;
+    run()?;
+    env.path.child("2025-01-06.md").assert(indoc! {"
+        ---
+        next: \"[[/2025-01-07|2025-01-07]]\"
+        prev: \"[[/2025-01-05|2025-01-05]]\"
+        ---
+    "});
+
+    std::fs::remove_file(env.path.child("2025-01-06.md"))?;
+    env.path
+        .child("journal-preparation-config.md")
+        .write_str(indoc! {r#"
+            ```toml
+            [day]
+            nav_link = true
+            nav_title = "relative"
+            ```
+        "#})?;
+    run()?;
+    env.path.child("2025-01-06.md").assert(indoc! {"
+        ---
+        next: \"[[/2025-01-07|Tomorrow]]\"
+        prev: \"[[/2025-01-05|Yesterday]]\"
+        ---
+    "});
+
+    std::fs::remove_file(env.path.child("2025-01-06.md"))?;
+    env.path
+        .child("journal-preparation-config.md")
+        .write_str(indoc! {r#"
+            ```toml
+            locale = "french"
+            [day]
+            nav_link = true
+            nav_title = "relative"
+            ```
+        "#})?;
+    run()?;
+    env.path.child("2025-01-06.md").assert(indoc! {"
+        ---
+        next: \"[[/2025-01-07|Demain]]\"
+        prev: \"[[/2025-01-05|Hier]]\"
+        ---
+    "});
+
+    Ok(())
+}
+
+#[test]
+fn content_separator() -> Result<()> {
+    let env = Env::new()?;
+    env.path
+        .child("journal-preparation-config.md")
+        .write_str(indoc! {r#"
+            ```toml
+            content_separator = "***"
+            ```
+        "#})?;
+    let week = env.path.child("2025/Week 03.md");
+    week.write_str("My notes\n")?;
+
+    for _ in 0..2 {
+        env.command()?
+            .args(["--from", "2025-01-15", "--to", "2025-01-15"])
+            .args(["--week", "week", "--no-day-page"])
+            .args(["--no-month-page", "--no-year-page"])
+            .assert()
+            .success()
+            .stderr(str::is_empty());
+    }
+
+    week.assert(indoc! {"
+        - Monday ![[/2025-01-13|2025-01-13]]
+        - Tuesday ![[/2025-01-14|2025-01-14]]
+        - Wednesday ![[/2025-01-15|2025-01-15]]
+        - Thursday ![[/2025-01-16|2025-01-16]]
+        - Friday ![[/2025-01-17|2025-01-17]]
+        - Saturday ![[/2025-01-18|2025-01-18]]
+        - Sunday ![[/2025-01-19|2025-01-19]]
+        ***
+        My notes
+    "});
+
+    Ok(())
+}
+
+#[test]
+fn event_priority() -> Result<()> {
+    let env = Env::new()?;
+    env.path.child("events/recurring.md").write_str(indoc! {r#"
+        ```toml
+        frequency = "daily"
+        content = "No priority"
+        ```
+
+        ```toml
+        frequency = "daily"
+        content = "Priority 1"
+        priority = 1
+        ```
+
+        ```toml
+        frequency = "daily"
+        content = "Priority 5"
+        priority = 5
+        ```
+
+        ```toml
+        frequency = "daily"
+        content = "Priority -1"
+        priority = -1
+        ```
+    "#})?;
+
+    env.command()?
+        .args(["--from", "2025-01-15", "--to", "2025-01-15"])
+        .args(["--day", "events"])
+        .assert()
+        .success()
+        .stderr(str::is_empty());
+
+    env.path.child("2025-01-15.md").assert(indoc! {"
+        Priority 5
+        Priority 1
+        No priority
+        Priority -1
+    "});
+
+    Ok(())
+}
+
+#[test]
+fn clean() -> Result<()> {
+    let env = Env::new()?;
+    let run = |args: &[&str]| -> Result<()> {
+        env.command()?
+            .args(["--from", "2025-01-15", "--to", "2025-01-16"])
+            .args(["--no-week-page", "--no-month-page", "--no-year-page"])
+            .args(args)
+            .assert()
+            .success();
+        Ok(())
+    };
+    let day1 = env.path.child("2025-01-15.md");
+    let day2 = env.path.child("2025-01-16.md");
+
+    run(&[])?;
+    day1.assert(predicates::path::exists());
+    day2.assert(predicates::path::exists());
+    std::fs::write(
+        day2.path(),
+        format!("{}My notes\n", std::fs::read_to_string(day2.path())?),
+    )?;
+
+    // Pages with other content are kept
+    run(&["--clean"])?;
+    day1.assert(predicates::path::missing());
+    day2.assert(str::ends_with("My notes\n"));
+
+    run(&["--clean", "--force"])?;
+    day2.assert(predicates::path::missing());
+
+    Ok(())
+}
+
+#[test]
+fn page_template() -> Result<()> {
+    let env = Env::new()?;
+    let template = env.path.child("templates/day.md");
+    template.write_str(indoc! {"
+        ---
+        tags: daily
+        ---
+        ## Notes of {date:%A %-d}
+    "})?;
+    env.path
+        .child("2025-01-14.md")
+        .write_str("Existing notes\n")?;
+
+    env.command()?
+        .args(["--from", "2025-01-14", "--to", "2025-01-15"])
+        .args([
+            "--day",
+            "day",
+            "--no-week-page",
+            "--no-month-page",
+            "--no-year-page",
+        ])
+        .arg("--page-template")
+        .arg(format!("day={}", template.path().display()))
+        .assert()
+        .success()
+        .stderr(str::is_empty());
+
+    env.path.child("2025-01-14.md").assert(indoc! {"
+        ---
+        day: Tuesday
+        ---
+        Existing notes
+    "});
+    env.path.child("2025-01-15.md").assert(indoc! {"
+        ---
+        day: Wednesday
+        tags: daily
+        ---
+        ## Notes of Wednesday 15
+    "});
+
+    // From the configuration, relative to the vault
+    env.path
+        .child("journal-preparation-config.md")
+        .write_str(indoc! {r#"
+            ```toml
+            [page_templates]
+            day = "templates/day.md"
+            ```
+        "#})?;
+    env.command()?
+        .args(["--from", "2025-01-16", "--to", "2025-01-16"])
+        .args([
+            "--day",
+            "day",
+            "--no-week-page",
+            "--no-month-page",
+            "--no-year-page",
+        ])
+        .assert()
+        .success()
+        .stderr(str::is_empty());
+    env.path
+        .child("2025-01-16.md")
+        .assert(str::ends_with("## Notes of Thursday 16\n"));
+
+    Ok(())
+}
+
+#[test]
+fn output_dir() -> Result<()> {
+    let env = Env::new()?;
+    let output = TempDir::new()?;
+    env.path.child("events/recurring.md").write_str(indoc! {r#"
+        ```toml
+        frequency = "daily"
+        content = "- [ ] Stretching"
+        ```
+    "#})?;
+
+    env.command()?
+        .args(["--from", "2025-01-15", "--to", "2025-01-15"])
+        .args([
+            "--day",
+            "events",
+            "--no-week-page",
+            "--no-month-page",
+            "--no-year-page",
+        ])
+        .arg("--output-dir")
+        .arg(output.path())
+        .assert()
+        .success()
+        .stderr(str::is_empty());
+
+    output.child("2025-01-15.md").assert("- [ ] Stretching\n");
+    env.path
+        .child("2025-01-15.md")
+        .assert(predicates::path::missing());
+
+    Ok(())
+}
+
+#[test]
+fn months_without_days() -> Result<()> {
+    fn files(env: &Env) -> Result<std::collections::BTreeMap<std::path::PathBuf, String>> {
+        let mut files = std::collections::BTreeMap::new();
+        for entry in walkdir::WalkDir::new(env.path.path()) {
+            let entry = entry?;
+            if entry.file_type().is_file() {
+                files.insert(
+                    entry.path().strip_prefix(env.path.path())?.to_path_buf(),
+                    std::fs::read_to_string(entry.path())?,
+                );
+            }
+        }
+        Ok(files)
+    }
+
+    let months = Env::new()?;
+    let days = Env::new()?;
+    for env in [&months, &days] {
+        env.path.child("events/recurring.md").write_str(indoc! {r#"
+                ```toml
+                frequency = "monthly"
+                monthdays = [15]
+                content = "Pay the rent"
+                ```
+            "#})?;
+    }
+
+    months
+        .command()?
+        .args(["--from", "2021-11-20", "--to", "2024-02-10"])
+        .args(["--no-day-page", "--no-week-page"])
+        .args(["--month", "month,nav,events", "--year", "month,nav,events"])
+        .assert()
+        .success()
+        .stderr(str::is_empty());
+    // Going through every day, with day pages only for the events
+    days.command()?
+        .args(["--from", "2021-11-20", "--to", "2024-02-10"])
+        .args(["--day", "events", "--no-week-page"])
+        .args(["--month", "month,nav,events", "--year", "month,nav,events"])
+        .assert()
+        .success()
+        .stderr(str::is_empty());
+
+    let months = files(&months)?;
+    let mut days = files(&days)?;
+    days.retain(|path, _| !path.to_string_lossy().ends_with("-15.md"));
+    assert_eq!(days, months);
+    assert!(months.contains_key(std::path::Path::new("2021.md")));
+    assert!(months.contains_key(std::path::Path::new("2021/November.md")));
+    assert!(months.contains_key(std::path::Path::new("2024/February.md")));
+    assert!(!months.contains_key(std::path::Path::new("2024/March.md")));
+
+    Ok(())
+}
+
+#[test]
+fn frontmatter_delimiter() -> Result<()> {
+    let env = Env::new()?;
+    env.path
+        .child("journal-preparation-config.md")
+        .write_str(indoc! {r#"
+            ```toml
+            frontmatter_delimiter = "+++"
+            ```
+        "#})?;
+    let day = env.path.child("2025-01-15.md");
+    day.write_str("+++\nmood = \"happy\"\n+++\nMy notes\n")?;
+
+    env.command()?
+        .args(["--from", "2025-01-15", "--to", "2025-01-15"])
+        .args(["--day", "day"])
+        .args(["--no-week-page", "--no-month-page", "--no-year-page"])
+        .assert()
+        .success()
+        .stderr(str::is_empty());
+
+    day.assert(indoc! {r#"
+        +++
+        day = "Wednesday"
+        mood = "happy"
+        +++
+        My notes
+    "#});
+
+    Ok(())
+}
+
+#[test]
+fn embed_prefix() -> Result<()> {
+    let env = Env::new()?;
+    env.path
+        .child("journal-preparation-config.md")
+        .write_str(indoc! {r#"
+            ```toml
+            embed_prefix = "!embed:"
+            ```
+        "#})?;
+
+    env.command()?
+        .args(["--from", "2025-01-06", "--to", "2025-01-06"])
+        .args(["--no-day-page", "--week", "week"])
+        .args(["--no-month-page", "--no-year-page"])
+        .assert()
+        .success()
+        .stderr(str::is_empty());
+
+    let week = env.path.child("2025/Week 02.md");
+    week.assert(str::starts_with(
+        "- Monday !embed:[[/2025-01-06|2025-01-06]]\n",
+    ));
+    week.assert(str::ends_with(
+        "- Sunday !embed:[[/2025-01-12|2025-01-12]]\n",
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn no_properties() -> Result<()> {
+    let env = Env::new()?;
+    env.path.child("events/recurring.md").write_str(indoc! {r#"
+        ```toml
+        frequency = "daily"
+        content = "- [ ] Stretching"
+        ```
+    "#})?;
+
+    env.command()?
+        .args(["--from", "2025-01-15", "--to", "2025-01-15"])
+        .args(["--no-properties", "--no-year-page"])
+        .assert()
+        .success()
+        .stderr(str::is_empty());
+
+    env.path.child("2025-01-15.md").assert("- [ ] Stretching\n");
+    env.path
+        .child("2025/Week 03.md")
+        .assert(str::starts_with("- Monday ![[/2025-01-13|2025-01-13]]\n"));
+    env.path
+        .child("2025/January.md")
+        .assert(str::starts_with("#### [[/2025/Week 01|Week 01]]\n"));
+
+    Ok(())
+}
+
+#[test]
+fn append_only() -> Result<()> {
+    let env = Env::new()?;
+    env.path.child("events/recurring.md").write_str(indoc! {r#"
+        ```toml
+        frequency = "daily"
+        content = "- [ ] Stretching"
+        ```
+        ```toml
+        frequency = "daily"
+        content = "- [ ] Reading"
+        ```
+    "#})?;
+    let original = indoc! {"
+        ---
+        day: Wednesday
+        ---
+        # Notes
+        - [ ] Reading
+        Some text
+    "};
+    env.path.child("2025-01-15.md").write_str(original)?;
+
+    env.command()?
+        .args(["--from", "2025-01-15", "--to", "2025-01-15"])
+        .args([
+            "--append-only",
+            "--no-week-page",
+            "--no-month-page",
+            "--no-year-page",
+        ])
+        .assert()
+        .success()
+        .stderr(str::is_empty());
+
+    env.path.child("2025-01-15.md").assert(indoc! {"
+        ---
+        day: Wednesday
+        week: \"[[/2025/Week 03|Week 03]]\"
+        month: \"[[/2025/January|January]]\"
+        next: \"[[/2025-01-16|2025-01-16]]\"
+        prev: \"[[/2025-01-14|2025-01-14]]\"
+        ---
+        # Notes
+        - [ ] Reading
+        Some text
+        - [ ] Stretching
+    "});
+
+    Ok(())
+}
+
+#[test]
+fn strict() -> Result<()> {
+    let env = Env::new()?;
+    env.path
+        .child("journal-preparation-config.md")
+        .write_str(indoc! {r#"
+            ```toml
+            event_files = ["events/work.md"]
+            ```
+        "#})?;
+    let run = |strict: bool| {
+        let mut command = env.command()?;
+        command.args(["--from", "2025-01-15", "--to", "2025-01-15"]);
+        if strict {
+            command.arg("--strict");
+        }
+        Ok::<_, anyhow::Error>(command.assert())
+    };
+
+    run(false)?.success().stderr(str::is_empty());
+    assert!(env.path.child("2025-01-15.md").path().exists());
+
+    std::fs::remove_file(env.path.child("2025-01-15.md").path())?;
+    run(true)?
+        .failure()
+        .stderr(str::contains("Event file not found: \"events/work.md\""));
+    assert!(!env.path.child("2025-01-15.md").path().exists());
+
+    env.path.child("events/work.md").touch()?;
+    run(true)?.success().stderr(str::is_empty());
+
+    env.path.child("events/work.md").write_str(indoc! {r#"
+        ```toml
+        frequency = "weekly"
+        content = "Foo"
+        ```
+    "#})?;
+    run(false)?.success();
+    assert!(env.path.child("2025-01-15.md").path().exists());
+    std::fs::remove_file(env.path.child("2025-01-15.md").path())?;
+    run(true)?
+        .failure()
+        .stderr(str::contains("events/work.md block 1"));
+    assert!(!env.path.child("2025-01-15.md").path().exists());
+
+    Ok(())
+}
+
+#[test]
+fn preview() -> Result<()> {
+    let env = Env::new()?;
+    env.path.child("events/recurring.md").write_str(indoc! {r#"
+        ```toml
+        frequency = "yearly"
+        yeardays = [359]
+        content = "Christmas"
+        ```
+    "#})?;
+
+    env.command()?
+        .args(["--preview", "2025-12-25"])
+        .assert()
+        .success()
+        .stdout(indoc! {"
+            ---
+            day: Thursday
+            week: \"[[/2025/Week 52|Week 52]]\"
+            month: \"[[/2025/December|December]]\"
+            next: \"[[/2025-12-26|2025-12-26]]\"
+            prev: \"[[/2025-12-24|2025-12-24]]\"
+            ---
+            Christmas
+        "})
+        .stderr(str::is_empty());
+
+    assert!(!env.path.child("2025-12-25.md").path().exists());
+    assert!(!env.path.child("2025/Week 52.md").path().exists());
+
+    Ok(())
+}
+
+#[test]
+fn exclude_weekends() -> Result<()> {
+    let env = Env::new()?;
+
+    env.command()?
+        .args(["--from", "2025-01-06", "--to", "2025-01-12"])
+        .args(["--exclude-weekends", "--month", "month", "--no-year-page"])
+        .assert()
+        .success()
+        .stderr(str::is_empty());
+
+    for day in 6..=10 {
+        env.path
+            .child(format!("2025-01-{day:02}.md"))
+            .assert(predicates::path::exists());
+    }
+    env.path
+        .child("2025-01-11.md")
+        .assert(predicates::path::missing());
+    env.path
+        .child("2025-01-12.md")
+        .assert(predicates::path::missing());
+    for page in ["2025/Week 02.md", "2025/January.md"] {
+        let content = std::fs::read_to_string(env.path.child(page).path())?;
+        assert!(content.contains("- Friday ![[/2025-01-10|2025-01-10]]"));
+        assert!(!content.contains("Saturday"), "{page}");
+        assert!(!content.contains("Sunday"), "{page}");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn exclude_weekend_days() -> Result<()> {
+    let env = Env::new()?;
+    env.path
+        .child("journal-preparation-config.md")
+        .write_str(indoc! {r#"
+            ```toml
+            exclude_weekends = true
+            weekend_days = ["Friday", "Saturday"]
+            ```
+        "#})?;
+
+    env.command()?
+        .args(["--from", "2025-01-06", "--to", "2025-01-12"])
+        .args(["--no-week-page", "--no-month-page", "--no-year-page"])
+        .assert()
+        .success()
+        .stderr(str::is_empty());
+
+    for day in [6, 7, 8, 9, 12] {
+        env.path
+            .child(format!("2025-01-{day:02}.md"))
+            .assert(predicates::path::exists());
+    }
+    for day in [10, 11] {
+        env.path
+            .child(format!("2025-01-{day:02}.md"))
+            .assert(predicates::path::missing());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn week_tasks() -> Result<()> {
+    let env = Env::new()?;
+    let week = env.path.child("2025/Week 02.md");
+    let run = || -> Result<()> {
+        env.command()?
+            .args(["--from", "2025-01-06", "--to", "2025-01-06"])
+            .args(["--no-day-page", "--no-month-page", "--no-year-page"])
+            .assert()
+            .success()
+            .stderr(str::is_empty());
+        Ok(())
+    };
+
+    env.path
+        .child("journal-preparation-config.md")
+        .write_str("```toml\n[week]\ntasks = true\n```\n")?;
+    run()?;
+    week.assert(indoc! {"
+        - [ ] [[/2025-01-06|2025-01-06]] review
+        - [ ] [[/2025-01-07|2025-01-07]] review
+        - [ ] [[/2025-01-08|2025-01-08]] review
+        - [ ] [[/2025-01-09|2025-01-09]] review
+        - [ ] [[/2025-01-10|2025-01-10]] review
+        - [ ] [[/2025-01-11|2025-01-11]] review
+        - [ ] [[/2025-01-12|2025-01-12]] review
+    "});
+
+    std::fs::remove_file(week.path())?;
+    env.path
+        .child("journal-preparation-config.md")
+        .write_str(indoc! {r#"
+            ```toml
+            [week]
+            week = true
+            tasks = true
+            task_label = "plan"
+            display_start = "Sunday"
+            ```
+        "#})?;
+    run()?;
+    week.assert(str::starts_with("- Sunday ![[/2025-01-12|2025-01-12]]\n"));
+    week.assert(str::contains(
+        "- Saturday ![[/2025-01-11|2025-01-11]]\n- [ ] [[/2025-01-12|2025-01-12]] plan\n",
+    ));
+    week.assert(str::ends_with("- [ ] [[/2025-01-11|2025-01-11]] plan\n"));
+
+    Ok(())
+}
+
+#[test]
+fn week_display_start() -> Result<()> {
+    let env = Env::new()?;
+    let week = env.path.child("2025/Week 02.md");
+    let run = || -> Result<()> {
+        env.command()?
+            .args(["--from", "2025-01-06", "--to", "2025-01-06"])
+            .args(["--no-day-page", "--no-month-page", "--no-year-page"])
+            .assert()
+            .success()
+            .stderr(str::is_empty());
+        Ok(())
+    };
+
+    env.path
+        .child("journal-preparation-config.md")
+        .write_str("```toml\n[week]\nweek = true\n```\n")?;
+    run()?;
+    week.assert(str::starts_with("- Monday ![[/2025-01-06|2025-01-06]]\n"));
+    week.assert(str::ends_with("- Sunday ![[/2025-01-12|2025-01-12]]\n"));
+
+    std::fs::remove_file(week.path())?;
+    env.path
+        .child("journal-preparation-config.md")
+        .write_str("```toml\n[week]\nweek = true\ndisplay_start = \"Sunday\"\n```\n")?;
+    run()?;
+    week.assert(str::starts_with("- Sunday ![[/2025-01-12|2025-01-12]]\n"));
+    week.assert(str::ends_with("- Saturday ![[/2025-01-11|2025-01-11]]\n"));
+
+    Ok(())
+}
@@ -0,0 +1,26 @@
+use chrono::NaiveDate;
+
+/// Source of the current date, so it can be pinned where the behavior depends on it
+pub trait Clock {
+    fn today(&self) -> NaiveDate;
+}
+
+/// Current date of the system, in UTC
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn today(&self) -> NaiveDate {
+        chrono::Utc::now().date_naive()
+    }
+}
+
+/// Clock stopped on the given date
+#[derive(Debug, Clone, Copy)]
+pub struct FixedClock(pub NaiveDate);
+
+impl Clock for FixedClock {
+    fn today(&self) -> NaiveDate {
+        self.0
+    }
+}
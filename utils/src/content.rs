@@ -6,10 +6,19 @@ use std::str::FromStr;
 mod codeblock;
 pub use codeblock::CodeBlock;
 
-#[derive(Debug)]
+/// Line before and after the properties, unless configured otherwise
+pub const DEFAULT_DELIMITER: &str = "---";
+
+/// Line before and after the properties written in TOML instead of YAML, e.g. in Hugo pages
+pub const TOML_DELIMITER: &str = "+++";
+
+#[derive(Debug, Clone)]
 pub struct Content {
     pub(super) properties: YamlOwned,
     pub(super) entries: VecDeque<Entry>,
+    /// Frontmatter that is not a YAML mapping, written back as is before the body
+    raw_properties: Option<String>,
+    delimiter: String,
 }
 
 impl Default for Content {
@@ -17,6 +26,8 @@ impl Default for Content {
         Self {
             properties: YamlOwned::Mapping(saphyr::MappingOwned::default()),
             entries: VecDeque::default(),
+            raw_properties: None,
+            delimiter: DEFAULT_DELIMITER.to_owned(),
         }
     }
 }
@@ -25,17 +36,191 @@ const fn to_yaml_str(string: String) -> YamlOwned {
     YamlOwned::Value(ScalarOwned::String(string))
 }
 
+/// Convert a TOML property to YAML, the dates being kept as their raw representation
+fn toml_to_yaml(value: toml::Value) -> YamlOwned {
+    match value {
+        toml::Value::String(value) => to_yaml_str(value),
+        toml::Value::Integer(value) => YamlOwned::Value(ScalarOwned::Integer(value)),
+        toml::Value::Float(value) => YamlOwned::Value(ScalarOwned::FloatingPoint(value.into())),
+        toml::Value::Boolean(value) => YamlOwned::Value(ScalarOwned::Boolean(value)),
+        toml::Value::Datetime(value) => {
+            YamlOwned::Representation(value.to_string(), saphyr::ScalarStyle::Plain, None)
+        }
+        toml::Value::Array(values) => {
+            YamlOwned::Sequence(values.into_iter().map(toml_to_yaml).collect())
+        }
+        toml::Value::Table(table) => YamlOwned::Mapping(
+            table
+                .into_iter()
+                .map(|(key, value)| (to_yaml_str(key), toml_to_yaml(value)))
+                .collect(),
+        ),
+    }
+}
+
+/// Convert a property to TOML, unless it has no TOML equivalent like a null value
+fn yaml_to_toml(value: &YamlOwned) -> Option<toml::Value> {
+    Some(match value {
+        YamlOwned::Value(ScalarOwned::String(value)) => toml::Value::String(value.clone()),
+        YamlOwned::Value(ScalarOwned::Integer(value)) => toml::Value::Integer(*value),
+        YamlOwned::Value(ScalarOwned::FloatingPoint(value)) => toml::Value::Float(**value),
+        YamlOwned::Value(ScalarOwned::Boolean(value)) => toml::Value::Boolean(*value),
+        YamlOwned::Representation(value, ..) => value.parse().map_or_else(
+            |_| toml::Value::String(value.clone()),
+            toml::Value::Datetime,
+        ),
+        YamlOwned::Sequence(values) => {
+            toml::Value::Array(values.iter().filter_map(yaml_to_toml).collect())
+        }
+        YamlOwned::Mapping(mapping) => toml::Value::Table(
+            mapping
+                .iter()
+                .filter_map(|(key, value)| Some((key.as_str()?.to_owned(), yaml_to_toml(value)?)))
+                .collect(),
+        ),
+        YamlOwned::Tagged(_, value) => yaml_to_toml(value)?,
+        _ => return None,
+    })
+}
+
+/// Key of a TOML property, quoted unless it is a bare key
+fn toml_key(key: &str) -> String {
+    if !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        key.to_owned()
+    } else {
+        toml::Value::String(key.to_owned()).to_string()
+    }
+}
+
+/// Properties emitted first and in this order, other properties follow alphabetically
+const PROPERTIES_ORDER: [&str; 5] = ["day", "week", "month", "next", "prev"];
+
+fn property_rank(key: &YamlOwned) -> (usize, Option<&str>) {
+    let key = key.as_str();
+    let index = key
+        .and_then(|key| {
+            PROPERTIES_ORDER
+                .iter()
+                .position(|property| *property == key)
+        })
+        .unwrap_or(PROPERTIES_ORDER.len());
+    (index, key)
+}
+
 impl Content {
+    /// Parse the content, with the properties between two `delimiter` lines, in TOML with the
+    /// `+++` delimiter and in YAML otherwise
+    ///
+    /// # Errors
+    /// - `ScanError` if the properties are not valid YAML
+    /// - `Toml` if the properties are not valid TOML
+    pub fn parse(string: &str, delimiter: &str) -> Result<Self, ContentError> {
+        use saphyr::LoadableYamlNode;
+
+        let mut content = Self::default();
+        content.set_delimiter(delimiter);
+        let mut lines = string.lines().peekable();
+
+        // If it starts with a delimiter, it means there is properties to read
+        if lines.next_if_eq(&delimiter).is_some() {
+            let mut properties = String::new();
+            for line in lines.by_ref() {
+                if line == delimiter {
+                    break;
+                }
+                properties = properties + line + "\n";
+            }
+
+            if delimiter == TOML_DELIMITER {
+                let table = properties
+                    .parse::<toml::Table>()
+                    .map_err(ContentError::Toml)?;
+                content.properties = toml_to_yaml(toml::Value::Table(table));
+                return Ok(content.parse_body(lines));
+            }
+
+            let mut yaml_documents =
+                YamlOwned::load_from_str(properties.as_str()).map_err(ContentError::ScanError)?;
+            if yaml_documents.len() > 1 {
+                // This shouldn't be possible as we read the content until the closing delimiter
+                // when it is the YAML document separator (---)
+                return Err(ContentError::MultipleYamlDocuments(properties));
+            }
+
+            if let Some(yaml) = yaml_documents.pop() {
+                if yaml.is_mapping() {
+                    content.properties = yaml;
+                } else {
+                    // Keep the frontmatter as is, so it isn't lost when writing the page back
+                    log::warn!("Properties are not a YAML mapping, ignoring them: {properties:?}");
+                    content.raw_properties = Some(properties);
+                }
+            }
+        }
+
+        Ok(content.parse_body(lines))
+    }
+
+    /// Add the lines after the properties as entries
+    fn parse_body<'a, I: Iterator<Item = &'a str>>(mut self, mut lines: I) -> Self {
+        while let Some(line) = lines.next() {
+            let entry = line.strip_prefix("```").map_or_else(
+                || line.to_owned().into(),
+                |kind| {
+                    let mut code = String::new();
+                    for line in lines.by_ref() {
+                        if line == "```" {
+                            break;
+                        }
+                        code = code + line + "\n";
+                    }
+
+                    CodeBlock::new(kind, code).into()
+                },
+            );
+
+            self.entries.push_back(entry);
+        }
+
+        self
+    }
+
+    /// Line written before and after the properties
+    pub(super) fn set_delimiter(&mut self, delimiter: &str) {
+        delimiter.clone_into(&mut self.delimiter);
+    }
+
+    #[must_use]
+    pub(super) fn delimiter(&self) -> &str {
+        &self.delimiter
+    }
+
     /// Insert the given property (key, value)
     ///
+    /// The frontmatter that is not a YAML mapping is left as is, without the property
+    ///
     /// Return value indicates if the content has been modified or not
     pub(super) fn insert_property(&mut self, key: String, value: String) -> bool {
+        self.insert_property_value(key, to_yaml_str(value))
+    }
+
+    pub(super) fn insert_property_value(&mut self, key: String, value: YamlOwned) -> bool {
+        if self.raw_properties.is_some() {
+            return false;
+        }
         let Some(mapping) = self.properties.as_mapping_mut() else {
             unreachable!()
         };
-        mapping
-            .insert(to_yaml_str(key), to_yaml_str(value.clone()))
-            .is_none_or(|previous_value| previous_value != to_yaml_str(value))
+        let key = to_yaml_str(key);
+        if mapping.get(&key) == Some(&value) {
+            return false;
+        }
+        mapping.insert(key, value);
+        true
     }
 
     #[must_use]
@@ -43,6 +228,27 @@ impl Content {
         self.properties.as_mapping_get(key)
     }
 
+    /// Properties with a stable ordering, so the output doesn't depend on insertion order
+    fn sorted_properties(&self) -> YamlOwned {
+        let Some(mapping) = self.properties.as_mapping() else {
+            unreachable!()
+        };
+        let mut properties = mapping.iter().collect::<Vec<_>>();
+        properties.sort_by(|(a, _), (b, _)| property_rank(a).cmp(&property_rank(b)));
+
+        YamlOwned::Mapping(
+            properties
+                .into_iter()
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect(),
+        )
+    }
+
+    #[must_use]
+    pub(super) fn contains_entry(&self, entry: &Entry) -> bool {
+        self.entries.contains(entry)
+    }
+
     /// Prepend the given entry if it is not already present
     ///
     /// Return value indicates if the content has been modified or not
@@ -77,13 +283,43 @@ impl Display for Content {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         use saphyr::{Yaml, YamlEmitter};
 
-        if !self.properties.is_empty_collection() {
-            YamlEmitter::new(f)
-                .dump(&Yaml::from(&self.properties))
+        if !self.properties.is_empty_collection() && self.delimiter == TOML_DELIMITER {
+            writeln!(f, "{}", self.delimiter)?;
+            if let Some(properties) = self.sorted_properties().as_mapping() {
+                for (key, value) in properties {
+                    if let (Some(key), Some(value)) = (key.as_str(), yaml_to_toml(value)) {
+                        writeln!(f, "{} = {value}", toml_key(key))?;
+                    }
+                }
+            }
+            writeln!(f, "{}", self.delimiter)?;
+        } else if !self.properties.is_empty_collection() {
+            let mut properties = String::new();
+            YamlEmitter::new(&mut properties)
+                .dump(&Yaml::from(&self.sorted_properties()))
                 .map_err(|_| std::fmt::Error)?;
-            writeln!(f, "\n---")?;
+            // The emitter starts the document with the YAML document separator
+            let properties = properties.strip_prefix("---").unwrap_or(&properties);
+            writeln!(f, "{}{properties}\n{}", self.delimiter, self.delimiter)?;
+        }
+        if let Some(properties) = &self.raw_properties {
+            write!(f, "{}\n{properties}{}\n", self.delimiter, self.delimiter)?;
         }
 
+        self.write_body(f)
+    }
+}
+
+impl Content {
+    /// Text of the entries, without the properties, as written in the page
+    #[must_use]
+    pub fn body_text(&self) -> String {
+        let mut body = String::new();
+        self.write_body(&mut body)
+            .map_or_else(|_| String::new(), |()| body)
+    }
+
+    fn write_body<W: std::fmt::Write>(&self, writer: &mut W) -> std::fmt::Result {
         let mut entries_started = false;
 
         for line in &self.entries {
@@ -93,7 +329,7 @@ impl Display for Content {
                 }
                 entries_started = true;
             }
-            writeln!(f, "{line}")?;
+            writeln!(writer, "{line}")?;
         }
 
         Ok(())
@@ -103,66 +339,15 @@ impl Display for Content {
 #[derive(Debug, derive_more::Error, derive_more::Display)]
 pub enum ContentError {
     MultipleYamlDocuments(#[error(ignore)] String),
-    NotAYamlMapping(#[error(ignore)] String),
     ScanError(saphyr::ScanError),
+    Toml(toml::de::Error),
 }
 
 impl FromStr for Content {
     type Err = ContentError;
 
     fn from_str(string: &str) -> Result<Self, Self::Err> {
-        use saphyr::LoadableYamlNode;
-
-        let mut content = Self::default();
-        let mut lines = string.lines().peekable();
-
-        // If it starts with a document separator, it means there is properties to read
-        if lines.next_if_eq(&"---").is_some() {
-            let mut properties = String::new();
-            for line in lines.by_ref() {
-                if line == "---" {
-                    break;
-                }
-                properties = properties + line + "\n";
-            }
-
-            let mut yaml_documents =
-                YamlOwned::load_from_str(properties.as_str()).map_err(ContentError::ScanError)?;
-            if yaml_documents.len() > 1 {
-                // This shouldn't be possible as we read the content until the second document
-                // separator (---)
-                return Err(ContentError::MultipleYamlDocuments(properties));
-            }
-
-            if let Some(yaml) = yaml_documents.pop() {
-                if yaml.is_mapping() {
-                    content.properties = yaml;
-                } else {
-                    return Err(ContentError::NotAYamlMapping(properties));
-                }
-            }
-        }
-
-        while let Some(line) = lines.next() {
-            let entry = line.strip_prefix("```").map_or_else(
-                || line.to_owned().into(),
-                |kind| {
-                    let mut code = String::new();
-                    for line in lines.by_ref() {
-                        if line == "```" {
-                            break;
-                        }
-                        code = code + line + "\n";
-                    }
-
-                    CodeBlock::new(kind, code).into()
-                },
-            );
-
-            content.entries.push_back(entry);
-        }
-
-        Ok(content)
+        Self::parse(string, DEFAULT_DELIMITER)
     }
 }
 
@@ -173,6 +358,55 @@ mod tests {
     use indoc::indoc;
     use saphyr::{Scalar, Yaml, Yaml::Value};
 
+    #[test]
+    fn body_text() {
+        let content = assert_ok!(Content::from_str(indoc! {r#"
+            ---
+            foo: bar
+            ---
+
+            Hello
+            ```toml
+            foo = "bar"
+            ```
+            World"#}));
+        assert_eq!(
+            indoc! {r#"
+                Hello
+                ```toml
+                foo = "bar"
+                ```
+                World
+            "#},
+            content.body_text()
+        );
+        assert_eq!("", Content::default().body_text());
+    }
+
+    #[test]
+    fn body_text_is_stable() {
+        let inputs = [
+            "",
+            "Hello World",
+            "\n\nHello\n\nWorld\n\n",
+            "---\nfoo: bar\n---\n",
+            "---\nfoo: bar\n---\nHello\n",
+            "---\n- foo\n---\nHello\n",
+            "```\ncode\n```\n",
+            "```toml\nunterminated = true\n",
+            "Hello\n---\nWorld\n",
+        ];
+
+        for input in inputs {
+            let content = assert_ok!(Content::from_str(input));
+            let written = content.to_string();
+            let reparsed = assert_ok!(Content::from_str(&written));
+
+            assert_eq!(content.body_text(), reparsed.body_text(), "{input:?}");
+            assert_eq!(written, reparsed.to_string(), "{input:?}");
+        }
+    }
+
     #[test]
     fn basic_document() {
         let string = "Hello World\n";
@@ -227,6 +461,32 @@ mod tests {
         assert_eq!(string, format!("{content}").as_str());
     }
 
+    #[test]
+    fn code_block_kinds_round_trip() {
+        for kind in ["", "sh", "yaml", "toml"] {
+            let string = format!("Before\n```{kind}\nfoo: bar\n```\nAfter\n");
+            let content = assert_ok!(Content::from_str(&string));
+            assert_eq!(content.entries.len(), 3);
+
+            let Entry::CodeBlock(ref code_block) = content.entries[1] else {
+                panic!("Code block not parsed as code block");
+            };
+
+            assert!(code_block.is_kind(kind), "{kind:?}");
+            assert_eq!(kind, code_block.kind());
+            assert_eq!(kind == "toml", code_block.is_toml());
+            for other in ["", "sh", "yaml", "toml"] {
+                assert_eq!(
+                    kind == other,
+                    code_block.is_kind(other),
+                    "{kind:?} {other:?}"
+                );
+            }
+
+            assert_eq!(string, content.to_string());
+        }
+    }
+
     #[test]
     fn parse_multiple_entries_and_remove_initial_empty_lines() {
         let string = indoc! {r"
@@ -281,7 +541,16 @@ mod tests {
         let content = assert_ok!(Content::from_str(string));
         assert!(!content.properties.is_empty_collection());
         assert_eq!(content.entries.len(), 0);
-        assert_eq!(string, format!("{content}").as_str());
+        assert_eq!(
+            indoc! {r"
+                ---
+                baz: 1
+                date: 2026-01-29
+                foo: bar
+                ---
+            "},
+            format!("{content}").as_str()
+        );
 
         let properties = Yaml::from(&content.properties);
         assert_eq!(
@@ -326,6 +595,85 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_toml_metadata() {
+        let string = indoc! {r#"
+            +++
+            title = "Notes"
+            date = 2026-01-29
+            tags = ["journal", "daily"]
+            draft = false
+            "my key" = 1.5
+            +++
+            Hello World
+        "#};
+        let mut content = assert_ok!(Content::parse(string, TOML_DELIMITER));
+        assert_eq!(content.entries.len(), 1);
+        assert_eq!(
+            Some("Notes"),
+            content.get_property("title").and_then(YamlOwned::as_str)
+        );
+
+        assert!(content.insert_property("day".to_owned(), "Thursday".to_owned()));
+        assert_eq!(
+            indoc! {r#"
+                +++
+                day = "Thursday"
+                date = 2026-01-29
+                draft = false
+                "my key" = 1.5
+                tags = ["journal", "daily"]
+                title = "Notes"
+                +++
+                Hello World
+            "#},
+            format!("{content}").as_str()
+        );
+
+        assert!(Content::parse("+++\nfoo: bar\n+++\n", TOML_DELIMITER).is_err());
+    }
+
+    #[test]
+    fn parse_scalar_metadata_as_raw_properties() {
+        let string = indoc! {r"
+            ---
+            just a scalar
+            ---
+            Hello World
+        "};
+
+        let mut content = assert_ok!(Content::from_str(string));
+        assert!(content.properties.is_empty_collection());
+        assert_eq!(content.entries.len(), 1);
+        assert_eq!(string, format!("{content}").as_str());
+
+        assert!(content.prepend_unique_entry(Entry::Line("First".to_owned())));
+        assert_eq!(
+            indoc! {r"
+                ---
+                just a scalar
+                ---
+                First
+                Hello World
+            "},
+            format!("{content}").as_str()
+        );
+
+        assert!(!content.insert_property("foo".to_owned(), "bar".to_owned()));
+        let expected = indoc! {r"
+            ---
+            just a scalar
+            ---
+            First
+            Hello World
+        "};
+        assert_eq!(expected, format!("{content}").as_str());
+
+        let content = assert_ok!(Content::from_str(expected));
+        assert_eq!(content.entries.len(), 2);
+        assert_eq!(expected, format!("{content}").as_str());
+    }
+
     #[test]
     fn insert_property_on_default_content() {
         let mut content = Content::default();
@@ -360,6 +708,65 @@ mod tests {
         );
     }
 
+    #[test]
+    fn insert_integer_property() {
+        let mut content = Content::default();
+        let value = YamlOwned::Value(ScalarOwned::Integer(53));
+        assert!(content.insert_property_value("week_number".to_owned(), value.clone()));
+        assert!(!content.insert_property_value("week_number".to_owned(), value));
+
+        let string = indoc! {r"
+            ---
+            week_number: 53
+            ---
+        "};
+        assert_eq!(string, format!("{content}").as_str());
+    }
+
+    #[test]
+    fn no_frontmatter_without_properties() {
+        let mut content = Content::default();
+        assert!(content.prepend_unique_entry(Entry::Line("Hello, World".to_owned())));
+
+        assert_eq!("Hello, World\n", format!("{content}").as_str());
+    }
+
+    #[test]
+    fn properties_order_does_not_depend_on_insertion() {
+        let properties = [
+            ("aliases", "Foo"),
+            ("prev", "Yesterday"),
+            ("next", "Tomorrow"),
+            ("month", "January"),
+            ("week", "Week 01"),
+            ("day", "Monday"),
+            ("author", "Me"),
+        ];
+        let expected = indoc! {r"
+            ---
+            day: Monday
+            week: Week 01
+            month: January
+            next: Tomorrow
+            prev: Yesterday
+            aliases: Foo
+            author: Me
+            ---
+        "};
+
+        let mut content = Content::default();
+        for (key, value) in properties {
+            content.insert_property(key.to_owned(), value.to_owned());
+        }
+        assert_eq!(expected, format!("{content}").as_str());
+
+        let mut content = Content::default();
+        for (key, value) in properties.into_iter().rev() {
+            content.insert_property(key.to_owned(), value.to_owned());
+        }
+        assert_eq!(expected, format!("{content}").as_str());
+    }
+
     #[test]
     fn prepend_unique_entry_on_default_content() {
         let mut content = Content::default();
@@ -67,4 +67,10 @@ impl CodeBlock {
     pub const fn is_toml(&self) -> bool {
         self.kind.is_toml()
     }
+
+    /// Whether the block is of the given kind, `""` being the kind of bare fences
+    #[must_use]
+    pub fn is_kind(&self, kind: &str) -> bool {
+        self.kind.as_str() == kind
+    }
 }
@@ -1,12 +1,63 @@
 use chrono::{Datelike, Days, IsoWeek, Months, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
 
-#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, derive_more::From, derive_more::Display)]
-#[display("{:04}", _0)]
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, derive_more::From)]
 pub struct Year(i32);
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+impl std::fmt::Display for Year {
+    /// At least 4 digits, e.g. `0850`, with a leading `-` for negative years like ISO 8601
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.0 < 0 {
+            write!(f, "-{:04}", self.0.unsigned_abs())
+        } else {
+            write!(f, "{:04}", self.0)
+        }
+    }
+}
+
+impl Year {
+    /// Number of ISO weeks of the year, 53 for the years with a week 53 and 52 otherwise
+    #[must_use]
+    #[allow(clippy::missing_panics_doc)]
+    pub fn num_weeks(self) -> u32 {
+        // December 28th is always in the last ISO week of its year
+        NaiveDate::from_ymd_opt(self.0, 12, 28)
+            .unwrap()
+            .iso_week()
+            .week()
+    }
+}
+
+#[derive(Debug, derive_more::Display, derive_more::Error)]
+#[display("Invalid year {_0:?}")]
+pub struct InvalidYear(#[error(ignore)] String);
+
+impl FromStr for Year {
+    type Err = InvalidYear;
+
+    /// Parse a year, e.g. `2026`
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        if string.is_empty() || !string.chars().all(|c| c.is_ascii_digit()) {
+            return Err(InvalidYear(string.to_owned()));
+        }
+        string
+            .parse::<i32>()
+            .map(Self)
+            .map_err(|_| InvalidYear(string.to_owned()))
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, derive_more::Display)]
 pub struct Monthday(u32);
 
+impl Monthday {
+    #[must_use]
+    pub const fn get(self) -> u32 {
+        self.0
+    }
+}
+
 #[derive(Debug, derive_more::Display, derive_more::Error)]
 #[display("Invalid month day {_0}")]
 pub struct InvalidMonthday(#[error(ignore)] u32);
@@ -25,13 +76,20 @@ impl TryFrom<u32> for Monthday {
 
 impl From<Monthday> for u32 {
     fn from(monthday: Monthday) -> Self {
-        monthday.0
+        monthday.get()
     }
 }
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, derive_more::Display)]
 pub struct Yearday(u32);
 
+impl Yearday {
+    #[must_use]
+    pub const fn get(self) -> u32 {
+        self.0
+    }
+}
+
 #[derive(Debug, derive_more::Display, derive_more::Error)]
 #[display("Invalid year day {_0}")]
 pub struct InvalidYearday(#[error(ignore)] u32);
@@ -50,11 +108,12 @@ impl TryFrom<u32> for Yearday {
 
 impl From<Yearday> for u32 {
     fn from(yearday: Yearday) -> Self {
-        yearday.0
+        yearday.get()
     }
 }
 
-#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, PartialOrd)]
+/// Month of a year, ordered by year then month
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
 pub struct Month {
     year: i32,
     month: u32,
@@ -69,6 +128,12 @@ impl Month {
         chrono::Month::try_from(self.month as u8).unwrap().name()
     }
 
+    /// Three letters abbreviation of the name, e.g. `Jan`
+    #[must_use]
+    pub fn name_abbrev(&self) -> &str {
+        &self.name()[..3]
+    }
+
     #[must_use]
     pub fn year(self) -> Year {
         self.year.into()
@@ -90,6 +155,48 @@ impl Month {
     }
 }
 
+#[derive(Debug, derive_more::Display, derive_more::Error)]
+#[display("Invalid month {_0:?}")]
+pub struct InvalidMonth(#[error(ignore)] String);
+
+impl FromStr for Month {
+    type Err = InvalidMonth;
+
+    /// Parse a month, either numeric (e.g. `2026-03`) or named (e.g. `2026/March`)
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        let invalid = || InvalidMonth(string.to_owned());
+
+        let month = if let Some((year, month)) = string.split_once('-') {
+            let month = month.parse::<u32>().map_err(|_| invalid())?;
+            (year, month)
+        } else if let Some((year, month)) = string.split_once('/') {
+            let month = month.parse::<chrono::Month>().map_err(|_| invalid())?;
+            (year, month.number_from_month())
+        } else {
+            return Err(invalid());
+        };
+
+        match month {
+            (year, month @ 1..=12) => Ok(Self {
+                year: Year::from_str(year).map_err(|_| invalid())?.0,
+                month,
+            }),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Month {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
 impl From<NaiveDate> for Month {
     fn from(date: NaiveDate) -> Self {
         Self {
@@ -103,6 +210,11 @@ impl From<IsoWeek> for Month {
         Self::from(week.first())
     }
 }
+impl From<Week> for Month {
+    fn from(week: Week) -> Self {
+        Self::from(week.first())
+    }
+}
 impl std::ops::Add<Months> for Month {
     type Output = Self;
 
@@ -128,6 +240,80 @@ impl std::ops::Sub<Months> for Month {
     }
 }
 
+/// Rule used to number the weeks of a year
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WeekNumbering {
+    /// ISO 8601, weeks start on Monday and the week 1 contains the first Thursday of the year
+    #[default]
+    Iso,
+    /// Weeks start on Sunday and the week 1 contains January 1st
+    Us,
+}
+
+impl WeekNumbering {
+    #[must_use]
+    pub const fn first_day(self) -> Weekday {
+        match self {
+            Self::Iso => Weekday::Mon,
+            Self::Us => Weekday::Sun,
+        }
+    }
+}
+
+/// Week of a year, numbered following a `WeekNumbering`
+#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd)]
+pub struct Week {
+    first: NaiveDate,
+    numbering: WeekNumbering,
+}
+
+impl Week {
+    /// Week containing the date
+    #[must_use]
+    pub fn new(date: NaiveDate, numbering: WeekNumbering) -> Self {
+        let days = date.weekday().days_since(numbering.first_day());
+        Self {
+            first: date - Days::new(days.into()),
+            numbering,
+        }
+    }
+
+    #[must_use]
+    pub const fn numbering(self) -> WeekNumbering {
+        self.numbering
+    }
+
+    /// Year the week belongs to, which can differ from the year of some of its days
+    #[must_use]
+    pub fn year(self) -> i32 {
+        match self.numbering {
+            WeekNumbering::Iso => self.first.iso_week().year(),
+            WeekNumbering::Us => self.last().year(),
+        }
+    }
+
+    /// Number of the week in its year, starting at 1
+    #[must_use]
+    pub fn week(self) -> u32 {
+        match self.numbering {
+            WeekNumbering::Iso => self.first.iso_week().week(),
+            // The last day is always in the year of the week, and the week 1 is the one whose
+            // last day falls in the first 7 days of the year
+            WeekNumbering::Us => self.last().ordinal0() / 7 + 1,
+        }
+    }
+}
+
+impl From<IsoWeek> for Week {
+    fn from(week: IsoWeek) -> Self {
+        Self {
+            first: week.first(),
+            numbering: WeekNumbering::Iso,
+        }
+    }
+}
+
 pub trait ToDateIterator: Sized {
     type Element: Navigation + std::cmp::PartialOrd + Clone;
 
@@ -152,6 +338,16 @@ impl ToDateIterator for IsoWeek {
         NaiveDate::from_isoywd_opt(self.year(), self.week(), Weekday::Sun).unwrap()
     }
 }
+impl ToDateIterator for Week {
+    type Element = NaiveDate;
+
+    fn first(&self) -> NaiveDate {
+        self.first
+    }
+    fn last(&self) -> NaiveDate {
+        self.first + Days::new(6)
+    }
+}
 impl ToDateIterator for Month {
     type Element = NaiveDate;
 
@@ -179,11 +375,22 @@ impl ToDateIterator for Year {
     }
 }
 
-pub trait Navigation {
+pub trait Navigation: Clone {
     #[must_use]
     fn next(&self) -> Self;
     #[must_use]
     fn prev(&self) -> Self;
+
+    /// Jump `n` steps forward, same as calling `next` `n` times
+    #[must_use]
+    fn nth_next(&self, n: u32) -> Self {
+        (0..n).fold(self.clone(), |value, _| value.next())
+    }
+    /// Jump `n` steps backward, same as calling `prev` `n` times
+    #[must_use]
+    fn nth_prev(&self, n: u32) -> Self {
+        (0..n).fold(self.clone(), |value, _| value.prev())
+    }
 }
 
 impl Navigation for NaiveDate {
@@ -193,6 +400,12 @@ impl Navigation for NaiveDate {
     fn prev(&self) -> Self {
         *self - Days::new(1)
     }
+    fn nth_next(&self, n: u32) -> Self {
+        *self + Days::new(n.into())
+    }
+    fn nth_prev(&self, n: u32) -> Self {
+        *self - Days::new(n.into())
+    }
 }
 
 impl Navigation for Month {
@@ -202,14 +415,27 @@ impl Navigation for Month {
     fn prev(&self) -> Self {
         *self - Months::new(1)
     }
+    fn nth_next(&self, n: u32) -> Self {
+        *self + Months::new(n)
+    }
+    fn nth_prev(&self, n: u32) -> Self {
+        *self - Months::new(n)
+    }
 }
 
+/// The years saturate at the bounds of `i32` instead of overflowing
 impl Navigation for Year {
     fn next(&self) -> Self {
-        Self(self.0 + 1)
+        self.nth_next(1)
     }
     fn prev(&self) -> Self {
-        Self(self.0 - 1)
+        self.nth_prev(1)
+    }
+    fn nth_next(&self, n: u32) -> Self {
+        Self(self.0.saturating_add_unsigned(n))
+    }
+    fn nth_prev(&self, n: u32) -> Self {
+        Self(self.0.saturating_sub_unsigned(n))
     }
 }
 
@@ -220,6 +446,33 @@ impl Navigation for IsoWeek {
     fn prev(&self) -> Self {
         (self.first() - Days::new(1)).iso_week()
     }
+    fn nth_next(&self, n: u32) -> Self {
+        (self.first() + Days::new(u64::from(n) * 7)).iso_week()
+    }
+    fn nth_prev(&self, n: u32) -> Self {
+        (self.first() - Days::new(u64::from(n) * 7)).iso_week()
+    }
+}
+
+impl Navigation for Week {
+    fn next(&self) -> Self {
+        self.nth_next(1)
+    }
+    fn prev(&self) -> Self {
+        self.nth_prev(1)
+    }
+    fn nth_next(&self, n: u32) -> Self {
+        Self {
+            first: self.first + Days::new(u64::from(n) * 7),
+            numbering: self.numbering,
+        }
+    }
+    fn nth_prev(&self, n: u32) -> Self {
+        Self {
+            first: self.first - Days::new(u64::from(n) * 7),
+            numbering: self.numbering,
+        }
+    }
 }
 
 pub struct DateIterator<'a, T, U>
@@ -280,6 +533,43 @@ where
     }
 }
 
+/// Replace the `{date}` tokens by the date, e.g. `2025-01-06`, and the `{date:<format>}` tokens by
+/// the date with the format of `chrono::format::strftime`
+///
+/// Any other text, including tokens with an invalid format, is kept as is
+#[must_use]
+pub fn replace_date_tokens(text: &str, date: NaiveDate) -> String {
+    use std::fmt::Write;
+
+    const TOKEN: &str = "{date";
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find(TOKEN) {
+        let (before, token) = rest.split_at(start);
+        result.push_str(before);
+        let tail = &token[TOKEN.len()..];
+        let format = tail.strip_prefix('}').map_or_else(
+            || tail.strip_prefix(':').and_then(|tail| tail.split_once('}')),
+            |tail| Some(("%Y-%m-%d", tail)),
+        );
+        let formatted = format.and_then(|(format, tail)| {
+            let mut formatted = String::new();
+            write!(formatted, "{}", date.format(format))
+                .ok()
+                .map(|()| (formatted, tail))
+        });
+        if let Some((formatted, tail)) = formatted {
+            result.push_str(&formatted);
+            rest = tail;
+        } else {
+            result.push('{');
+            rest = &token[1..];
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -305,6 +595,102 @@ mod tests {
         assert_eq!(31, build_month(2025, 12).num_days());
     }
 
+    #[test]
+    fn month_name_abbrev() {
+        let names = [
+            "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+        ];
+        for (month, name) in (1..=12).zip(names) {
+            assert_eq!(name, build_month(2025, month).name_abbrev());
+        }
+    }
+
+    #[test]
+    fn monthday_and_yearday() {
+        let monthday = Monthday::try_from(15).unwrap();
+        assert_eq!(15, monthday.get());
+        assert_eq!(15, u32::from(monthday));
+        assert_eq!("15", monthday.to_string());
+
+        let yearday = Yearday::try_from(366).unwrap();
+        assert_eq!(366, yearday.get());
+        assert_eq!(366, u32::from(yearday));
+        assert_eq!("366", yearday.to_string());
+    }
+
+    #[test]
+    fn year_from_str() {
+        assert_eq!(Year::from(2026), "2026".parse::<Year>().unwrap());
+        assert_eq!(Year::from(987), "0987".parse::<Year>().unwrap());
+        assert!("".parse::<Year>().is_err());
+        assert!("-2026".parse::<Year>().is_err());
+        assert!("2026-03".parse::<Year>().is_err());
+        assert!("foo".parse::<Year>().is_err());
+    }
+
+    #[test]
+    fn year_num_weeks() {
+        assert_eq!(53, Year::from(2020).num_weeks());
+        assert_eq!(52, Year::from(2021).num_weeks());
+        assert_eq!(52, Year::from(2025).num_weeks());
+        assert_eq!(53, Year::from(2026).num_weeks());
+    }
+
+    #[test]
+    fn year_ord() {
+        let mut years = [2026, -50, 2025, 850].map(Year::from);
+        years.sort();
+        assert_eq!([-50, 850, 2025, 2026].map(Year::from), years);
+    }
+
+    #[test]
+    fn month_ord() {
+        let month = |year, month| Month { year, month };
+        let mut months = vec![
+            month(2026, 1),
+            month(2025, 12),
+            month(2025, 2),
+            month(2024, 11),
+            month(2026, 3),
+        ];
+        months.sort();
+        assert_eq!(
+            vec![
+                month(2024, 11),
+                month(2025, 2),
+                month(2025, 12),
+                month(2026, 1),
+                month(2026, 3),
+            ],
+            months
+        );
+        assert_eq!(Some(&month(2026, 3)), months.iter().max());
+    }
+
+    #[test]
+    fn year_display() {
+        assert_eq!("2026", Year::from(2026).to_string());
+        assert_eq!("0850", Year::from(850).to_string());
+        assert_eq!("0001", Year::from(1).to_string());
+        assert_eq!("0000", Year::from(0).to_string());
+        assert_eq!("-0050", Year::from(-50).to_string());
+        assert_eq!("12345", Year::from(12345).to_string());
+    }
+
+    #[test]
+    fn month_from_str() {
+        assert_eq!(build_month(2026, 3), "2026-03".parse::<Month>().unwrap());
+        assert_eq!(build_month(2026, 12), "2026-12".parse::<Month>().unwrap());
+        assert_eq!(build_month(2026, 3), "2026/March".parse::<Month>().unwrap());
+        assert_eq!(build_month(2026, 3), "2026/march".parse::<Month>().unwrap());
+        assert!("2026".parse::<Month>().is_err());
+        assert!("2026-00".parse::<Month>().is_err());
+        assert!("2026-13".parse::<Month>().is_err());
+        assert!("2026-03-01".parse::<Month>().is_err());
+        assert!("2026/Foo".parse::<Month>().is_err());
+        assert!("foo-03".parse::<Month>().is_err());
+    }
+
     #[test]
     fn month_arithmetic() {
         let month = Month::from(NaiveDate::from_ymd_opt(2024, 12, 1).unwrap());
@@ -313,6 +699,82 @@ mod tests {
         assert_eq!(build_month(2023, 12), month - Months::new(12));
     }
 
+    mod week {
+        use super::*;
+
+        /// Check the year and number of the week of each date
+        fn assert_weeks(numbering: WeekNumbering, expected: &[(&str, i32, u32)]) {
+            for &(date, year, number) in expected {
+                let date = date.parse::<NaiveDate>().unwrap();
+                let week = Week::new(date, numbering);
+                assert_eq!((year, number), (week.year(), week.week()), "{date}");
+                assert!(week.first() <= date && date <= week.last());
+                assert_eq!(numbering.first_day(), week.first().weekday());
+            }
+        }
+
+        #[test]
+        fn iso() {
+            assert_weeks(
+                WeekNumbering::Iso,
+                &[
+                    // 2023-01-01 is a Sunday
+                    ("2022-12-31", 2022, 52),
+                    ("2023-01-01", 2022, 52),
+                    ("2023-01-02", 2023, 1),
+                    ("2023-01-08", 2023, 1),
+                    ("2023-01-09", 2023, 2),
+                    // 2025-01-01 is a Wednesday
+                    ("2024-12-29", 2024, 52),
+                    ("2024-12-30", 2025, 1),
+                    ("2025-01-05", 2025, 1),
+                    ("2025-01-06", 2025, 2),
+                ],
+            );
+        }
+
+        #[test]
+        fn us() {
+            assert_weeks(
+                WeekNumbering::Us,
+                &[
+                    // 2023-01-01 is a Sunday
+                    ("2022-12-31", 2022, 53),
+                    ("2023-01-01", 2023, 1),
+                    ("2023-01-07", 2023, 1),
+                    ("2023-01-08", 2023, 2),
+                    // 2025-01-01 is a Wednesday
+                    ("2024-12-28", 2024, 52),
+                    ("2024-12-29", 2025, 1),
+                    ("2025-01-04", 2025, 1),
+                    ("2025-01-05", 2025, 2),
+                    ("2025-12-27", 2025, 52),
+                    ("2025-12-28", 2026, 1),
+                ],
+            );
+        }
+
+        #[test]
+        fn navigation() {
+            let week = Week::new(
+                NaiveDate::from_ymd_opt(2024, 12, 28).unwrap(),
+                WeekNumbering::Us,
+            );
+            assert_eq!((2025, 1), (week.next().year(), week.next().week()));
+            assert_eq!((2024, 51), (week.prev().year(), week.prev().week()));
+            assert_eq!(7, week.iter().count());
+
+            let iso_week = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap().iso_week();
+            let week = Week::from(iso_week);
+            assert_eq!(
+                (iso_week.year(), iso_week.week()),
+                (week.year(), week.week())
+            );
+            assert_eq!(iso_week.first(), week.first());
+            assert_eq!(iso_week.last(), week.last());
+        }
+    }
+
     mod to_date_iterator {
         use super::*;
 
@@ -354,6 +816,42 @@ mod tests {
     mod navigation {
         use super::*;
 
+        fn steps<T: Navigation>(value: &T, n: u32, step: fn(&T) -> T) -> T {
+            (0..n).fold(value.clone(), |value, _| step(&value))
+        }
+
+        #[test]
+        fn nth_next_and_nth_prev() {
+            let date = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap();
+            let week = date.iso_week();
+            let month = Month::from(date);
+            let year = Year::from(2024);
+
+            for n in [0, 1, 13, 60] {
+                assert_eq!(steps(&date, n, NaiveDate::next), date.nth_next(n));
+                assert_eq!(steps(&date, n, NaiveDate::prev), date.nth_prev(n));
+                assert_eq!(steps(&week, n, IsoWeek::next), week.nth_next(n));
+                assert_eq!(steps(&week, n, IsoWeek::prev), week.nth_prev(n));
+                assert_eq!(steps(&month, n, Month::next), month.nth_next(n));
+                assert_eq!(steps(&month, n, Month::prev), month.nth_prev(n));
+                assert_eq!(steps(&year, n, Year::next), year.nth_next(n));
+                assert_eq!(steps(&year, n, Year::prev), year.nth_prev(n));
+            }
+
+            assert_eq!(build_month(2026, 1), month.nth_next(13));
+            assert_eq!(build_month(2023, 11), month.nth_prev(13));
+
+            assert_eq!(
+                Year::from(i32::MAX),
+                Year::from(i32::MAX - 1).nth_next(u32::MAX)
+            );
+            assert_eq!(
+                Year::from(i32::MIN),
+                Year::from(i32::MIN + 1).nth_prev(u32::MAX)
+            );
+            assert_eq!(Year::from(i32::MAX), Year::from(i32::MAX).next());
+        }
+
         #[test]
         fn week() {
             let week = NaiveDate::from_ymd_opt(2024, 9, 24).unwrap().iso_week();
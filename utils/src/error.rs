@@ -0,0 +1,36 @@
+use crate::events::InvalidEvent;
+use crate::page::PageError;
+use std::path::PathBuf;
+
+/// Failure reading the configuration page
+#[derive(Debug, derive_more::From, derive_more::Display, derive_more::Error)]
+pub enum ConfigError {
+    ReadingFile(PageError),
+    Toml(toml::de::Error),
+}
+
+/// Failure of the preparation, wrapped in the `anyhow::Error` returned by the preparer so callers
+/// can tell them apart with `downcast_ref`
+#[derive(Debug, derive_more::From, derive_more::Display, derive_more::Error)]
+pub enum PrepareError {
+    #[from]
+    Config(ConfigError),
+    #[from]
+    Page(PageError),
+    #[from]
+    Event(InvalidEvent),
+    #[display("Error creating dir {}: {_0}", _1.display())]
+    CreatingDir(std::io::Error, PathBuf),
+    #[display("Error reading file {}: {_0}", _1.display())]
+    ReadingFile(std::io::Error, PathBuf),
+    #[display("Error writing file {}: {_0}", _1.display())]
+    WritingFile(std::io::Error, PathBuf),
+    #[display("Error parsing daily notes config {}: {_0}", _1.display())]
+    DailyNotesConfig(serde_json::Error, PathBuf),
+    #[display("Event file not found: {_0:?}")]
+    EventFileNotFound(#[error(ignore)] String),
+    #[display("Template not found: {}", _0.display())]
+    TemplateNotFound(#[error(ignore)] PathBuf),
+    #[display("Error running post hook {_1:?}: {_0}")]
+    PostHook(std::io::Error, #[error(ignore)] String),
+}
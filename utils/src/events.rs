@@ -1,52 +1,204 @@
 use crate::content::CodeBlock;
-use chrono::NaiveDate;
-use serde::{Deserialize, Serialize};
+use crate::date::{Month, ToDateIterator, Year, replace_date_tokens};
+use chrono::{NaiveDate, Weekday};
+use serde::{Deserialize, Deserializer, Serialize};
 
 pub mod recurrence;
-use recurrence::SerdeRecurrence;
+use recurrence::{DEFAULT_WEEKEND_DAYS, SerdeRecurrence};
 pub use recurrence::{InvalidRecurrence, Recurrence};
 
 /// Describe a recurring event
 #[derive(Debug, Clone)]
 pub struct Event {
     recurrence: Recurrence,
-    pub content: String,
+    /// Lines added to the matching pages
+    pub content: Vec<String>,
+    /// Name of the note embedded in the matching pages, after the content
+    embed: Option<String>,
     validity: DateRange,
+    /// The validity starts on the date the events are read
+    from_today: bool,
     exceptions: Vec<DateRange>,
+    tags: Vec<String>,
+    icon: Option<String>,
+    except_holidays: bool,
+    /// Shared holidays the event does not occur on, kept apart from its own exceptions
+    holidays: Vec<NaiveDate>,
+    priority: Option<i32>,
+    /// Days that are not business days, shared by all the events
+    weekend_days: Vec<Weekday>,
 }
 
+/// List or task markers kept before the icon of an event
+const LINE_MARKERS: [&str; 5] = ["- [ ] ", "- [x] ", "- [X] ", "- ", "* "];
+
 impl Event {
     #[must_use]
     pub fn date(date: NaiveDate, content: String) -> Self {
         Self {
             recurrence: Recurrence::Once(vec![date]),
-            content,
+            content: vec![content],
+            embed: None,
             validity: DateRange::default(),
+            from_today: false,
             exceptions: vec![],
+            tags: vec![],
+            icon: None,
+            except_holidays: false,
+            holidays: vec![],
+            priority: None,
+            weekend_days: DEFAULT_WEEKEND_DAYS.to_vec(),
+        }
+    }
+
+    /// Name of the note to embed in the matching pages, if any
+    #[must_use]
+    pub fn embed(&self) -> Option<&str> {
+        self.embed.as_deref()
+    }
+
+    /// Events with a higher priority are rendered first, the events without priority counting as 0
+    #[must_use]
+    pub const fn priority(&self) -> Option<i32> {
+        self.priority
+    }
+
+    /// Add the shared holidays, if the event does not occur on holidays
+    pub fn add_holidays(&mut self, holidays: &[NaiveDate]) {
+        if self.except_holidays {
+            self.holidays.extend_from_slice(holidays);
         }
     }
+
+    /// Use the shared weekend days to count the business days
+    pub fn set_weekend_days(&mut self, weekend_days: &[Weekday]) {
+        weekend_days.clone_into(&mut self.weekend_days);
+    }
+
+    /// Use the range as validity if the event sets neither bound of its own validity
+    pub fn set_default_validity(&mut self, range: &DateRange) {
+        if self.validity.from.is_none() && self.validity.to.is_none() && !self.from_today {
+            self.validity = range.clone();
+        }
+    }
+
+    /// Start the validity on the given date, if the event is valid from `"today"`
+    pub const fn set_today(&mut self, today: NaiveDate) {
+        if self.from_today {
+            self.validity.from = Some(today);
+        }
+    }
+
+    #[must_use]
+    pub fn with_icon(self, icon: impl Into<String>) -> Self {
+        Self {
+            icon: Some(icon.into()),
+            ..self
+        }
+    }
+
+    /// Lines of content to add to the pages of the date, prefixed by the icon if any
+    ///
+    /// The icon is placed after the list or task marker of the line, if any. The `{date}` and
+    /// `{date:<format>}` tokens are replaced by the date.
+    #[must_use]
+    pub fn lines(&self, date: NaiveDate) -> impl DoubleEndedIterator<Item = String> + '_ {
+        self.content.iter().map(move |line| {
+            let line = replace_date_tokens(line, date);
+            match &self.icon {
+                Some(icon) => {
+                    let marker_len = LINE_MARKERS
+                        .iter()
+                        .find(|marker| line.starts_with(*marker))
+                        .map_or(0, |marker| marker.len());
+                    let (marker, text) = line.split_at(marker_len);
+                    format!("{marker}{icon} {text}")
+                }
+                None => line,
+            }
+        })
+    }
+
+    #[must_use]
+    pub const fn recurrence(&self) -> &Recurrence {
+        &self.recurrence
+    }
 }
 
 impl TryFrom<SerdeEvent> for Event {
-    type Error = InvalidRecurrence;
+    type Error = InvalidEvent;
 
     fn try_from(event: SerdeEvent) -> Result<Self, Self::Error> {
+        // An empty `content` is still accepted, only a missing one is an error
+        let embed = event.embed.filter(|embed| !embed.is_empty());
+        if embed.is_none() && event.content.is_empty() {
+            return Err(InvalidEvent::ContentRequired);
+        }
+        let content = Vec::from(event.content);
+        let (from, from_today) = match (event.validity.from, event.validity.from_month) {
+            (Some(_), Some(_)) => return Err(InvalidEvent::ConflictingValidity("from")),
+            (Some(SerdeValidityStart::Date(date)), None) => (Some(date), false),
+            (Some(SerdeValidityStart::Keyword(ValidityKeyword::Today)), None) => (None, true),
+            (None, month) => (month.map(|month| month.first()), false),
+        };
+        let to = match (event.validity.to, event.validity.to_month) {
+            (Some(_), Some(_)) => return Err(InvalidEvent::ConflictingValidity("to")),
+            (to, None) => to,
+            (None, Some(month)) => Some(month.last()),
+        };
+        let validity = DateRange { from, to };
+        let exceptions = event
+            .exceptions
+            .into_iter()
+            .filter(|exception| {
+                let overlaps = exception.overlaps(&validity);
+                if !overlaps {
+                    log::warn!(
+                        "Exception {exception} is outside of the validity {validity}, ignoring it"
+                    );
+                }
+                overlaps
+            })
+            .collect();
         Ok(Self {
             recurrence: Recurrence::try_from(event.recurrence)?,
-            content: event.content,
-            validity: event.validity,
-            exceptions: event.exceptions,
+            content,
+            embed,
+            validity,
+            from_today,
+            exceptions,
+            tags: event.tags,
+            icon: event.icon,
+            except_holidays: event.except_holidays,
+            holidays: vec![],
+            priority: event.priority,
+            weekend_days: DEFAULT_WEEKEND_DAYS.to_vec(),
         })
     }
 }
 
 impl From<Event> for SerdeEvent {
     fn from(event: Event) -> Self {
+        let from = if event.from_today {
+            Some(SerdeValidityStart::Keyword(ValidityKeyword::Today))
+        } else {
+            event.validity.from.map(SerdeValidityStart::Date)
+        };
         Self {
             recurrence: event.recurrence.into(),
-            content: event.content,
-            validity: event.validity,
+            content: event.content.into(),
+            embed: event.embed,
+            validity: SerdeValidity {
+                from,
+                to: event.validity.to,
+                from_month: None,
+                to_month: None,
+            },
             exceptions: event.exceptions,
+            tags: event.tags,
+            icon: event.icon,
+            except_holidays: event.except_holidays,
+            priority: event.priority,
         }
     }
 }
@@ -56,11 +208,119 @@ impl From<Event> for SerdeEvent {
 pub struct SerdeEvent {
     #[serde(flatten)]
     recurrence: SerdeRecurrence,
-    content: String,
+    #[serde(default, skip_serializing_if = "SerdeContent::is_empty")]
+    content: SerdeContent,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    embed: Option<String>,
     #[serde(flatten)]
-    validity: DateRange,
-    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    validity: SerdeValidity,
+    #[serde(
+        default,
+        skip_serializing_if = "Vec::is_empty",
+        deserialize_with = "deserialize_exceptions"
+    )]
     exceptions: Vec<DateRange>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    icon: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    except_holidays: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    priority: Option<i32>,
+}
+
+/// Validity of an event, which can start on the date the events are read with `from = "today"`
+///
+/// The validity can also start on the first day of a month with `from_month = "2025-03"`, and end
+/// on the last day of a month with `to_month`.
+#[derive(Debug, Serialize, Deserialize)]
+struct SerdeValidity {
+    from: Option<SerdeValidityStart>,
+    to: Option<NaiveDate>,
+    #[serde(default, skip_serializing)]
+    from_month: Option<Month>,
+    #[serde(default, skip_serializing)]
+    to_month: Option<Month>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+enum SerdeValidityStart {
+    Date(NaiveDate),
+    Keyword(ValidityKeyword),
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum ValidityKeyword {
+    Today,
+}
+
+/// Content is either a single string or an array of lines
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+enum SerdeContent {
+    Line(String),
+    Lines(Vec<String>),
+}
+
+impl Default for SerdeContent {
+    fn default() -> Self {
+        Self::Lines(vec![])
+    }
+}
+
+impl SerdeContent {
+    const fn is_empty(&self) -> bool {
+        matches!(self, Self::Lines(lines) if lines.is_empty())
+    }
+}
+
+impl From<SerdeContent> for Vec<String> {
+    fn from(content: SerdeContent) -> Self {
+        match content {
+            SerdeContent::Line(line) => vec![line],
+            SerdeContent::Lines(lines) => lines,
+        }
+    }
+}
+
+impl From<Vec<String>> for SerdeContent {
+    fn from(mut lines: Vec<String>) -> Self {
+        if lines.len() == 1 {
+            Self::Line(lines.remove(0))
+        } else {
+            Self::Lines(lines)
+        }
+    }
+}
+
+/// An exception is either a single date or a range of dates
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SerdeException {
+    Date(NaiveDate),
+    Range(DateRange),
+}
+
+impl From<SerdeException> for DateRange {
+    fn from(exception: SerdeException) -> Self {
+        match exception {
+            SerdeException::Date(date) => date.into(),
+            SerdeException::Range(range) => range,
+        }
+    }
+}
+
+fn deserialize_exceptions<'de, D>(deserializer: D) -> Result<Vec<DateRange>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(Vec::<SerdeException>::deserialize(deserializer)?
+        .into_iter()
+        .map(DateRange::from)
+        .collect())
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -71,12 +331,52 @@ pub struct DateRange {
     pub to: Option<NaiveDate>,
 }
 
+impl From<NaiveDate> for DateRange {
+    fn from(date: NaiveDate) -> Self {
+        Self {
+            from: Some(date),
+            to: Some(date),
+        }
+    }
+}
+
 impl DateRange {
     #[must_use]
     pub fn contains(&self, date: NaiveDate) -> bool {
         (self.from.is_none() || self.from <= Some(date))
             && (self.to.is_none() || self.to >= Some(date))
     }
+
+    /// Check if both ranges share at least one date, a missing bound being open-ended
+    #[must_use]
+    pub fn overlaps(&self, other: &Self) -> bool {
+        fn starts_before_end(range: &DateRange, other: &DateRange) -> bool {
+            match (range.from, other.to) {
+                (Some(from), Some(to)) => from <= to,
+                _ => true,
+            }
+        }
+
+        starts_before_end(self, other) && starts_before_end(other, self)
+    }
+}
+
+impl std::fmt::Display for DateRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.from, self.to) {
+            (Some(from), Some(to)) if from == to => write!(f, "{from}"),
+            (from, to) => {
+                if let Some(from) = from {
+                    write!(f, "{from}")?;
+                }
+                write!(f, "..")?;
+                if let Some(to) = to {
+                    write!(f, "{to}")?;
+                }
+                Ok(())
+            }
+        }
+    }
 }
 
 impl Event {
@@ -91,30 +391,152 @@ impl Event {
                 return false;
             }
         }
+        if self.holidays.contains(&date) {
+            return false;
+        }
+
+        self.recurrence
+            .matches_with_weekend_days(date, &self.weekend_days)
+    }
+
+    /// List the dates the event occurs on during the year, taking validity and exceptions into
+    /// account
+    #[must_use]
+    pub fn occurrences_in(&self, year: Year) -> Vec<NaiveDate> {
+        let (from, to) = (year.first().first(), year.last().last());
+        from.iter_days()
+            .take_while(|date| *date <= to)
+            .filter(|date| self.matches(*date))
+            .collect()
+    }
+
+    /// Check if the event occurs on a finite list of dates, either because of its recurrence or
+    /// because its validity has both bounds
+    #[must_use]
+    pub const fn is_bounded(&self) -> bool {
+        self.recurrence.is_bounded() || (self.validity.from.is_some() && self.validity.to.is_some())
+    }
+
+    /// List all the dates the event occurs on, taking validity and exceptions into account
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the event is not bounded, use `occurrences_in` instead
+    pub fn occurrences(&self) -> Result<Vec<NaiveDate>, UnboundedEvent> {
+        if !self.is_bounded() {
+            return Err(UnboundedEvent);
+        }
 
-        self.recurrence.matches(date)
+        let mut dates = match (&self.recurrence, self.validity.from, self.validity.to) {
+            (_, Some(from), Some(to)) => from.iter_days().take_while(|date| *date <= to).collect(),
+            (Recurrence::Once(dates), _, _) => dates.clone(),
+            _ => unreachable!("checked by is_bounded"),
+        };
+        dates.retain(|date| self.matches(*date));
+        dates.sort_unstable();
+        dates.dedup();
+        Ok(dates)
+    }
+
+    /// Check if the event occurs on any date between `from` and `to`, both inclusive
+    #[must_use]
+    pub fn matches_range(&self, from: NaiveDate, to: NaiveDate) -> bool {
+        from.iter_days()
+            .take_while(|date| *date <= to)
+            .any(|date| self.matches(date))
+    }
+
+    /// Check if the event has at least one of the given tags
+    ///
+    /// An empty filter matches all events
+    #[must_use]
+    pub fn matches_tags(&self, tags: &[String]) -> bool {
+        tags.is_empty() || self.tags.iter().any(|tag| tags.contains(tag))
     }
 }
 
+/// Convert a YAML event to a TOML value, so both are deserialized the same way
+fn yaml_to_toml(yaml: saphyr::YamlOwned) -> Result<toml::Value, InvalidEvent> {
+    use saphyr::{ScalarOwned, YamlOwned};
+
+    Ok(match yaml {
+        YamlOwned::Value(ScalarOwned::Boolean(value)) => toml::Value::Boolean(value),
+        YamlOwned::Value(ScalarOwned::Integer(value)) => toml::Value::Integer(value),
+        YamlOwned::Value(ScalarOwned::FloatingPoint(value)) => toml::Value::Float(*value),
+        YamlOwned::Value(ScalarOwned::String(value)) => toml::Value::String(value),
+        YamlOwned::Sequence(values) => toml::Value::Array(
+            values
+                .into_iter()
+                .map(yaml_to_toml)
+                .collect::<Result<_, _>>()?,
+        ),
+        YamlOwned::Mapping(mapping) => toml::Value::Table(
+            mapping
+                .into_iter()
+                .map(|(key, value)| match key.as_str() {
+                    Some(key) => Ok((key.to_owned(), yaml_to_toml(value)?)),
+                    None => Err(InvalidEvent::UnsupportedYaml(format!("key {key:?}"))),
+                })
+                .collect::<Result<_, _>>()?,
+        ),
+        yaml => return Err(InvalidEvent::UnsupportedYaml(format!("{yaml:?}"))),
+    })
+}
+
 #[derive(Debug, derive_more::From, derive_more::Display, derive_more::Error)]
 pub enum InvalidEvent {
-    #[display("Not a toml block")]
-    NotAtTomlBlock,
+    #[display("Not a toml or yaml block")]
+    NotAnEventBlock,
     #[display("Deserialization error: {_0}")]
     TomlError(toml::de::Error),
+    #[display("YAML error: {_0}")]
+    YamlError(saphyr::ScanError),
+    #[display("Unsupported YAML value: {_0}")]
+    #[from(ignore)]
+    UnsupportedYaml(#[error(not(source))] String),
     #[display("Invalid recurrence: {_0}")]
     InvalidRecurrence(InvalidRecurrence),
+    #[display("`content` or `embed` must be specified")]
+    ContentRequired,
+    #[display("`{_0}` and `{_0}_month` cannot both be specified")]
+    #[from(ignore)]
+    ConflictingValidity(#[error(not(source))] &'static str),
+}
+
+#[derive(Debug, derive_more::Display, derive_more::Error)]
+#[display("Cannot list the occurrences of an event without `from` and `to`")]
+pub struct UnboundedEvent;
+
+/// Language of the code block, `yml` being the same as `yaml`
+fn block_lang(kind: &str) -> &str {
+    if kind == "yml" { "yaml" } else { kind }
+}
+
+/// Check if the code block is written in the language of the event blocks, `yml` and `yaml`
+/// being the same
+#[must_use]
+pub fn is_event_block(block: &CodeBlock, lang: &str) -> bool {
+    block_lang(block.kind()) == block_lang(lang)
 }
 
 impl TryFrom<&CodeBlock> for Event {
     type Error = InvalidEvent;
 
     fn try_from(block: &CodeBlock) -> Result<Self, Self::Error> {
-        if !block.is_toml() {
-            return Err(InvalidEvent::NotAtTomlBlock);
-        }
-        let event: SerdeEvent = toml::from_str(block.code())?;
-        Ok(event.try_into()?)
+        let event: SerdeEvent = match block_lang(block.kind()) {
+            "toml" => toml::from_str(block.code())?,
+            "yaml" => {
+                use saphyr::LoadableYamlNode;
+
+                let value = saphyr::YamlOwned::load_from_str(block.code())?
+                    .into_iter()
+                    .next()
+                    .map_or_else(|| Ok(toml::Value::Table(toml::Table::new())), yaml_to_toml)?;
+                SerdeEvent::deserialize(value)?
+            }
+            _ => return Err(InvalidEvent::NotAnEventBlock),
+        };
+        event.try_into()
     }
 }
 
@@ -128,6 +550,41 @@ mod tests {
         assert_err!(Event::try_from(&CodeBlock::new("foo", "")));
     }
 
+    #[test]
+    fn yaml() {
+        let event = assert_ok!(Event::try_from(&CodeBlock::new(
+            "yaml",
+            indoc::indoc! {r#"
+                frequency: weekly
+                weekdays: [Monday, Friday]
+                content:
+                  - "- [ ] Foo"
+                  - "- [ ] Bar"
+                from: 2025-01-01
+                exceptions: [2025-01-03]
+                priority: 2
+            "#},
+        )));
+        assert_eq!(
+            Recurrence::Weekly(vec![chrono::Weekday::Mon, chrono::Weekday::Fri]),
+            event.recurrence
+        );
+        assert_eq!(
+            vec!["- [ ] Foo".to_owned(), "- [ ] Bar".to_owned()],
+            event.content
+        );
+        assert_eq!(Some(2), event.priority());
+        assert!(!event.matches(NaiveDate::from_ymd_opt(2024, 12, 30).unwrap()));
+        assert!(!event.matches(NaiveDate::from_ymd_opt(2025, 1, 3).unwrap()));
+        assert!(event.matches(NaiveDate::from_ymd_opt(2025, 1, 6).unwrap()));
+
+        assert_err!(Event::try_from(&CodeBlock::new(
+            "yaml",
+            "frequency: [daily"
+        )));
+        assert_err!(Event::try_from(&CodeBlock::new("yaml", "content: ~")));
+    }
+
     #[test]
     fn no_frequency() {
         assert_err!(Event::try_from(&CodeBlock::toml(r#"content = "foo""#)));
@@ -138,6 +595,126 @@ mod tests {
         assert_err!(Event::try_from(&CodeBlock::toml(r#"frequency = "daily""#)));
     }
 
+    #[test]
+    fn round_trip_all_recurrences() {
+        use crate::date::{Monthday, Yearday};
+        use chrono::Weekday;
+        use recurrence::WeekIndex;
+
+        let date = |d| NaiveDate::from_ymd_opt(2025, 3, d).unwrap();
+        let recurrences = [
+            Recurrence::Daily,
+            Recurrence::Weekly(vec![Weekday::Mon, Weekday::Fri]),
+            Recurrence::Monthly(vec![
+                assert_ok!(Monthday::try_from(1)),
+                assert_ok!(Monthday::try_from(31)),
+            ]),
+            Recurrence::RelativeMonthly(vec![Weekday::Tue], vec![WeekIndex::Last]),
+            Recurrence::RelativeMonthly(
+                vec![Weekday::Mon, Weekday::Thu],
+                vec![WeekIndex::Second, WeekIndex::Fourth],
+            ),
+            Recurrence::BusinessMonthly(3),
+            Recurrence::Yearly(vec![
+                assert_ok!(Yearday::try_from(1)),
+                assert_ok!(Yearday::try_from(366)),
+            ]),
+            Recurrence::Once(vec![date(1), date(31)]),
+        ];
+
+        for recurrence in recurrences {
+            let mut event = Event::date(date(1), "Foo".to_owned());
+            event.recurrence = recurrence.clone();
+            event.validity = DateRange {
+                from: Some(date(1)),
+                to: Some(date(31)),
+            };
+            event.exceptions = vec![date(15).into()];
+            event.tags = vec!["work".to_owned()];
+            event.priority = Some(2);
+
+            let serialized = assert_ok!(toml::to_string(&SerdeEvent::from(event)));
+            let deserialized = assert_ok!(toml::from_str::<SerdeEvent>(&serialized));
+            let event = assert_ok!(Event::try_from(deserialized));
+
+            assert_eq!(recurrence, event.recurrence, "{serialized}");
+            assert_eq!(vec!["Foo".to_owned()], event.content);
+            assert_eq!(Some(date(1)), event.validity.from);
+            assert_eq!(Some(date(31)), event.validity.to);
+            assert_eq!(1, event.exceptions.len());
+            assert_eq!(Some(date(15)), event.exceptions[0].from);
+            assert_eq!(Some(date(15)), event.exceptions[0].to);
+            assert_eq!(vec!["work".to_owned()], event.tags);
+            assert_eq!(Some(2), event.priority);
+        }
+    }
+
+    #[test]
+    fn serialize_relative_monthly() {
+        use chrono::Weekday;
+        use recurrence::WeekIndex;
+
+        let mut event = Event::date(
+            NaiveDate::from_ymd_opt(2025, 3, 1).unwrap(),
+            "Foo".to_owned(),
+        );
+        event.recurrence = Recurrence::RelativeMonthly(vec![Weekday::Sun], vec![WeekIndex::Last]);
+
+        assert_eq!(
+            indoc::indoc! {r#"
+                frequency = "monthly"
+                weekdays = ["Sunday"]
+                index = "last"
+                content = "Foo"
+            "#},
+            assert_ok!(toml::to_string(&SerdeEvent::from(event)))
+        );
+    }
+
+    #[test]
+    fn embed() {
+        let event = assert_ok!(Event::try_from(&CodeBlock::toml(
+            r#"
+                frequency = "daily"
+                embed = "Morning routine"
+            "#,
+        )));
+        assert!(event.content.is_empty());
+        assert_eq!(Some("Morning routine"), event.embed());
+
+        let serialized = assert_ok!(toml::to_string(&SerdeEvent::from(event)));
+        assert!(serialized.contains(r#"embed = "Morning routine""#));
+        assert!(!serialized.contains("content"));
+
+        let event = assert_ok!(Event::try_from(&CodeBlock::toml(
+            r#"
+                frequency = "daily"
+                content = "- [ ] Stretching"
+                embed = "Morning routine"
+            "#,
+        )));
+        assert_eq!(vec!["- [ ] Stretching".to_owned()], event.content);
+        assert_eq!(Some("Morning routine"), event.embed());
+
+        assert!(matches!(
+            Event::try_from(&CodeBlock::toml(
+                r#"
+                frequency = "daily"
+                embed = ""
+            "#,
+            )),
+            Err(InvalidEvent::ContentRequired)
+        ));
+
+        let event = assert_ok!(Event::try_from(&CodeBlock::toml(
+            r#"
+                frequency = "daily"
+                content = ""
+            "#,
+        )));
+        assert_eq!(vec![String::new()], event.content);
+    }
+
     #[test]
     fn simple() {
         let event = assert_ok!(Event::try_from(&CodeBlock::toml(
@@ -147,7 +724,254 @@ mod tests {
             "#,
         )));
         assert!(matches!(event.recurrence, Recurrence::Daily));
-        assert_eq!("Foo", event.content);
+        assert_eq!(vec!["Foo".to_owned()], event.content);
+    }
+
+    #[test]
+    fn content_lines() {
+        let event = assert_ok!(Event::try_from(&CodeBlock::toml(
+            r#"
+                frequency = "daily"
+                content = ["- [ ] Foo", "- [ ] Bar"]
+            "#,
+        )));
+        assert_eq!(
+            vec!["- [ ] Foo".to_owned(), "- [ ] Bar".to_owned()],
+            event.content
+        );
+
+        let serialized = assert_ok!(toml::to_string(&SerdeEvent::from(event)));
+        assert!(serialized.contains(r#"content = ["- [ ] Foo", "- [ ] Bar"]"#));
+
+        let event = Event::date(
+            NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            "Foo".to_owned(),
+        );
+        let serialized = assert_ok!(toml::to_string(&SerdeEvent::from(event)));
+        assert!(serialized.contains(r#"content = "Foo""#));
+    }
+
+    #[test]
+    fn except_holidays() {
+        let date = |day| NaiveDate::from_ymd_opt(2025, 12, day).unwrap();
+        let holidays = [date(25)];
+
+        let mut event = assert_ok!(Event::try_from(&CodeBlock::toml(
+            r#"
+                frequency = "daily"
+                content = "Foo"
+            "#,
+        )));
+        event.add_holidays(&holidays);
+        assert!(event.matches(date(25)));
+
+        let mut event = assert_ok!(Event::try_from(&CodeBlock::toml(
+            r#"
+                frequency = "daily"
+                content = "Foo"
+                except_holidays = true
+            "#,
+        )));
+        assert!(event.matches(date(25)));
+        event.add_holidays(&holidays);
+        assert!(event.matches(date(24)));
+        assert!(!event.matches(date(25)));
+        assert!(event.matches(date(26)));
+
+        // The holidays are not written back as exceptions of the event
+        let serialized = assert_ok!(toml::to_string(&SerdeEvent::from(event)));
+        assert!(!serialized.contains("exceptions"));
+        assert!(!serialized.contains("2025-12-25"));
+    }
+
+    #[test]
+    fn exceptions_outside_validity() {
+        let event = assert_ok!(Event::try_from(&CodeBlock::toml(
+            r#"
+                frequency = "daily"
+                content = "Foo"
+                from = "2025-08-01"
+                to = "2025-08-31"
+                exceptions = ["2025-08-15", "2025-09-15"]
+            "#,
+        )));
+        assert_eq!(1, event.exceptions.len());
+        assert_eq!(
+            Some(NaiveDate::from_ymd_opt(2025, 8, 15).unwrap()),
+            event.exceptions[0].from
+        );
+    }
+
+    #[test]
+    fn weekend_days() {
+        let date = |day| NaiveDate::from_ymd_opt(2026, 8, day).unwrap();
+        let mut event = assert_ok!(Event::try_from(&CodeBlock::toml(
+            r#"
+                frequency = "monthly"
+                business_day = 1
+                content = "Foo"
+            "#,
+        )));
+        assert!(event.matches(date(3)));
+
+        event.set_weekend_days(&[Weekday::Fri, Weekday::Sat]);
+        assert!(!event.matches(date(1)));
+        assert!(event.matches(date(2)));
+        assert!(!event.matches(date(3)));
+    }
+
+    #[test]
+    fn occurrences_in() {
+        let date = |year, month, day| NaiveDate::from_ymd_opt(year, month, day).unwrap();
+
+        let monthly = assert_ok!(Event::try_from(&CodeBlock::toml(
+            r#"
+                frequency = "monthly"
+                monthdays = [15]
+                content = "Foo"
+            "#,
+        )));
+        let occurrences = monthly.occurrences_in(Year::from(2025));
+        assert_eq!(12, occurrences.len());
+        assert_eq!(date(2025, 1, 15), occurrences[0]);
+        assert_eq!(date(2025, 12, 15), occurrences[11]);
+
+        let limited = assert_ok!(Event::try_from(&CodeBlock::toml(
+            r#"
+                frequency = "monthly"
+                monthdays = [15]
+                content = "Foo"
+                from = "2025-03-01"
+                exceptions = ["2025-06-15"]
+            "#,
+        )));
+        assert_eq!(9, limited.occurrences_in(Year::from(2025)).len());
+        assert!(limited.occurrences_in(Year::from(2024)).is_empty());
+
+        let once = Event::date(date(2025, 2, 3), "Foo".to_owned());
+        assert_eq!(
+            vec![date(2025, 2, 3)],
+            once.occurrences_in(Year::from(2025))
+        );
+        assert!(once.occurrences_in(Year::from(2026)).is_empty());
+    }
+
+    #[test]
+    fn occurrences() {
+        let date = |year, month, day| NaiveDate::from_ymd_opt(year, month, day).unwrap();
+
+        let unbounded = assert_ok!(Event::try_from(&CodeBlock::toml(
+            r#"
+                frequency = "monthly"
+                monthdays = [15]
+                content = "Foo"
+                from = "2025-03-01"
+            "#,
+        )));
+        assert!(!unbounded.is_bounded());
+        assert_err!(unbounded.occurrences());
+
+        let bounded = assert_ok!(Event::try_from(&CodeBlock::toml(
+            r#"
+                frequency = "monthly"
+                monthdays = [15]
+                content = "Foo"
+                from = "2025-03-01"
+                to = "2025-06-30"
+                exceptions = ["2025-05-15"]
+            "#,
+        )));
+        assert!(bounded.is_bounded());
+        assert_eq!(
+            vec![date(2025, 3, 15), date(2025, 4, 15), date(2025, 6, 15)],
+            assert_ok!(bounded.occurrences())
+        );
+
+        let once = assert_ok!(Event::try_from(&CodeBlock::toml(
+            r#"
+                frequency = "once"
+                dates = ["2025-04-01", "2025-02-03", "2026-01-01"]
+                content = "Foo"
+                to = "2025-12-31"
+            "#,
+        )));
+        assert!(once.is_bounded());
+        assert_eq!(
+            vec![date(2025, 2, 3), date(2025, 4, 1)],
+            assert_ok!(once.occurrences())
+        );
+    }
+
+    #[test]
+    fn icon() {
+        let event = assert_ok!(Event::try_from(&CodeBlock::toml(
+            r#"
+                frequency = "daily"
+                content = "Foo"
+                icon = "🎂"
+            "#,
+        )));
+        let date = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        assert_eq!(
+            vec!["🎂 Foo".to_owned()],
+            event.lines(date).collect::<Vec<_>>()
+        );
+
+        let event = Event::date(date, "Foo".to_owned());
+        assert_eq!(
+            vec!["Foo".to_owned()],
+            event.lines(date).collect::<Vec<_>>()
+        );
+
+        let event = event.with_icon("🎂");
+        let serialized = assert_ok!(toml::to_string(&SerdeEvent::from(event)));
+        assert!(serialized.contains(r#"icon = "🎂""#));
+    }
+
+    #[test]
+    fn icon_task() {
+        let event = assert_ok!(Event::try_from(&CodeBlock::toml(
+            r#"
+                frequency = "daily"
+                content = ["- [ ] Foo", "- [x] Bar", "- Baz"]
+                icon = "🎂"
+            "#,
+        )));
+        assert_eq!(
+            vec![
+                "- [ ] 🎂 Foo".to_owned(),
+                "- [x] 🎂 Bar".to_owned(),
+                "- 🎂 Baz".to_owned()
+            ],
+            event
+                .lines(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn date_tokens() {
+        let event = assert_ok!(Event::try_from(&CodeBlock::toml(
+            r#"
+                frequency = "daily"
+                content = [
+                    "- [ ] Review notes from {date}",
+                    "Week {date:%V} of {date:%Y}",
+                    "{date:%Q} {day} {date {date:%Y",
+                ]
+                icon = "📝"
+            "#,
+        )));
+        assert_eq!(
+            vec![
+                "- [ ] 📝 Review notes from 2025-01-06".to_owned(),
+                "📝 Week 02 of 2025".to_owned(),
+                "📝 {date:%Q} {day} {date {date:%Y".to_owned(),
+            ],
+            event
+                .lines(NaiveDate::from_ymd_opt(2025, 1, 6).unwrap())
+                .collect::<Vec<_>>()
+        );
     }
 
     #[test]
@@ -163,4 +987,206 @@ mod tests {
         assert_eq!("2025-01-01".parse().ok(), event.validity.from);
         assert_eq!("2025-01-31".parse().ok(), event.validity.to);
     }
+
+    #[test]
+    fn month_dates() {
+        let event = assert_ok!(Event::try_from(&CodeBlock::toml(
+            r#"
+                frequency = "daily"
+                content = "Foo"
+                from_month = "2025-03"
+                to_month = "2025-03"
+            "#,
+        )));
+        let date = |m, d| NaiveDate::from_ymd_opt(2024, m, d).unwrap();
+        assert_eq!("2025-03-01".parse().ok(), event.validity.from);
+        assert_eq!("2025-03-31".parse().ok(), event.validity.to);
+
+        let event = assert_ok!(Event::try_from(&CodeBlock::toml(
+            r#"
+                frequency = "daily"
+                content = "Foo"
+                from = "2024-01-15"
+                to_month = "2024/February"
+            "#,
+        )));
+        assert!(!event.matches(date(1, 14)));
+        assert!(event.matches(date(1, 15)));
+        assert!(event.matches(date(2, 29)));
+        assert!(!event.matches(date(3, 1)));
+
+        assert_err!(Event::try_from(&CodeBlock::toml(
+            r#"
+                frequency = "daily"
+                content = "Foo"
+                from = "2025-03-02"
+                from_month = "2025-03"
+            "#,
+        )));
+        assert_err!(Event::try_from(&CodeBlock::toml(
+            r#"
+                frequency = "daily"
+                content = "Foo"
+                to_month = "2025-13"
+            "#,
+        )));
+    }
+
+    #[test]
+    fn from_today() {
+        let mut event = assert_ok!(Event::try_from(&CodeBlock::toml(
+            r#"
+                frequency = "daily"
+                content = "Foo"
+                from = "today"
+            "#,
+        )));
+        let date = |d| NaiveDate::from_ymd_opt(2025, 3, d).unwrap();
+
+        event.set_today(date(10));
+        assert!(!event.matches(date(9)));
+        assert!(event.matches(date(10)));
+        assert!(event.matches(date(11)));
+
+        let serialized = assert_ok!(toml::to_string(&SerdeEvent::from(event)));
+        assert!(serialized.contains(r#"from = "today""#));
+
+        assert_err!(Event::try_from(&CodeBlock::toml(
+            r#"
+                frequency = "daily"
+                content = "Foo"
+                from = "tomorrow"
+            "#,
+        )));
+    }
+
+    #[test]
+    fn exceptions() {
+        let event = assert_ok!(Event::try_from(&CodeBlock::toml(
+            r#"
+                frequency = "daily"
+                content = "Foo"
+                exceptions = [
+                    "2025-12-25",
+                    { from = "2025-08-01", to = "2025-08-15" },
+                    "2026-01-01",
+                ]
+            "#,
+        )));
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+        assert_eq!(3, event.exceptions.len());
+        assert!(!event.matches(date(2025, 12, 25)));
+        assert!(event.matches(date(2025, 12, 24)));
+        assert!(event.matches(date(2025, 12, 26)));
+        assert!(!event.matches(date(2026, 1, 1)));
+        assert!(event.matches(date(2025, 7, 31)));
+        assert!(!event.matches(date(2025, 8, 1)));
+        assert!(!event.matches(date(2025, 8, 15)));
+        assert!(event.matches(date(2025, 8, 16)));
+    }
+
+    #[test]
+    fn date_range_overlaps() {
+        let date = |m, d| NaiveDate::from_ymd_opt(2025, m, d);
+        let range = |from, to| DateRange { from, to };
+
+        let open = DateRange::default();
+        let august = range(date(8, 1), date(8, 31));
+        assert!(open.overlaps(&open));
+        assert!(open.overlaps(&august));
+        assert!(august.overlaps(&open));
+
+        // Half-open
+        assert!(range(date(8, 31), None).overlaps(&august));
+        assert!(!range(date(9, 1), None).overlaps(&august));
+        assert!(range(None, date(8, 1)).overlaps(&august));
+        assert!(!range(None, date(7, 31)).overlaps(&august));
+        assert!(range(None, date(8, 1)).overlaps(&range(date(8, 1), None)));
+        assert!(!range(None, date(7, 31)).overlaps(&range(date(8, 1), None)));
+
+        // Bounded
+        assert!(august.overlaps(&DateRange::from(date(8, 15).unwrap())));
+        assert!(august.overlaps(&range(date(7, 1), date(9, 30))));
+        assert!(range(date(7, 1), date(8, 1)).overlaps(&august));
+        assert!(!range(date(7, 1), date(7, 31)).overlaps(&august));
+        assert!(!august.overlaps(&range(date(9, 1), date(9, 30))));
+
+        assert_eq!("2025-08-01..2025-08-31", august.to_string());
+        assert_eq!(
+            "2025-08-15",
+            DateRange::from(date(8, 15).unwrap()).to_string()
+        );
+        assert_eq!("..2025-07-31", range(None, date(7, 31)).to_string());
+        assert_eq!("..", open.to_string());
+    }
+
+    #[test]
+    fn invalid_exception() {
+        assert_err!(Event::try_from(&CodeBlock::toml(
+            r#"
+                frequency = "daily"
+                content = "Foo"
+                exceptions = ["foo"]
+            "#,
+        )));
+    }
+
+    #[test]
+    fn matches_range() {
+        let event = assert_ok!(Event::try_from(&CodeBlock::toml(
+            r#"
+                frequency = "yearly"
+                yeardays = [32, 60]
+                content = "Foo"
+                exceptions = [{ from = "2026-03-01", to = "2026-03-01" }]
+            "#,
+        )));
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+        assert!(event.matches_range(date(2025, 1, 1), date(2025, 12, 31)));
+        assert!(event.matches_range(date(2025, 3, 1), date(2025, 3, 31)));
+        assert!(event.matches_range(date(2026, 2, 1), date(2026, 2, 28)));
+        assert!(!event.matches_range(date(2026, 3, 1), date(2026, 3, 31)));
+        assert!(!event.matches_range(date(2026, 4, 1), date(2026, 12, 31)));
+    }
+
+    #[test]
+    fn tags() {
+        let work = assert_ok!(Event::try_from(&CodeBlock::toml(
+            r#"
+                frequency = "daily"
+                content = "Stand-up"
+                tags = ["work"]
+            "#,
+        )));
+        let personal = assert_ok!(Event::try_from(&CodeBlock::toml(
+            r#"
+                frequency = "daily"
+                content = "Stretching"
+                tags = ["personal", "health"]
+            "#,
+        )));
+        let untagged = assert_ok!(Event::try_from(&CodeBlock::toml(
+            r#"
+                frequency = "daily"
+                content = "Foo"
+            "#,
+        )));
+        assert_eq!(vec!["work".to_owned()], work.tags);
+
+        let filter = vec!["work".to_owned()];
+        assert!(work.matches_tags(&filter));
+        assert!(!personal.matches_tags(&filter));
+        assert!(!untagged.matches_tags(&filter));
+
+        let filter = vec!["health".to_owned(), "work".to_owned()];
+        assert!(work.matches_tags(&filter));
+        assert!(personal.matches_tags(&filter));
+        assert!(!untagged.matches_tags(&filter));
+
+        assert!(work.matches_tags(&[]));
+        assert!(personal.matches_tags(&[]));
+        assert!(untagged.matches_tags(&[]));
+    }
 }
@@ -1,8 +1,21 @@
 use crate::date::{InvalidMonthday, InvalidYearday, Month, Monthday, Yearday};
+use crate::locale::Locale;
 use chrono::{Datelike, NaiveDate, Weekday};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Default, Serialize, Deserialize, derive_more::IsVariant)]
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Serialize,
+    Deserialize,
+    derive_more::IsVariant,
+)]
 #[serde(rename_all = "snake_case")]
 pub enum Frequency {
     #[default]
@@ -13,6 +26,19 @@ pub enum Frequency {
     Once,
 }
 
+impl Frequency {
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Daily => "daily",
+            Self::Weekly => "weekly",
+            Self::Monthly => "monthly",
+            Self::Yearly => "yearly",
+            Self::Once => "once",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, derive_more::IsVariant)]
 #[serde(rename_all = "snake_case")]
 pub enum WeekIndex {
@@ -23,6 +49,22 @@ pub enum WeekIndex {
     Last,
 }
 
+impl WeekIndex {
+    #[must_use]
+    pub const fn name(&self) -> &str {
+        match self {
+            Self::First => "first",
+            Self::Second => "second",
+            Self::Third => "third",
+            Self::Fourth => "fourth",
+            Self::Last => "last",
+        }
+    }
+}
+
+/// Days of the weekends, not counted as business days unless configured otherwise
+pub const DEFAULT_WEEKEND_DAYS: [Weekday; 2] = [Weekday::Sat, Weekday::Sun];
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum Recurrence {
     Daily,
@@ -30,8 +72,10 @@ pub enum Recurrence {
     Weekly(Vec<Weekday>),
     /// Monthly each Nth day, starting from 1
     Monthly(Vec<Monthday>),
-    /// Relative monthly, e.g. each First Monday
-    RelativeMonthly(Vec<Weekday>, WeekIndex),
+    /// Relative monthly, e.g. each First and Third Monday
+    RelativeMonthly(Vec<Weekday>, Vec<WeekIndex>),
+    /// Monthly each Nth business day (Monday to Friday by default), starting from 1
+    BusinessMonthly(u32),
     /// Yearly each Nth day, starting from 1
     Yearly(Vec<Yearday>),
     /// Once on specific dates
@@ -40,8 +84,15 @@ pub enum Recurrence {
 
 impl Recurrence {
     #[must_use]
-    #[allow(clippy::missing_panics_doc)]
     pub fn matches(&self, date: NaiveDate) -> bool {
+        self.matches_with_weekend_days(date, &DEFAULT_WEEKEND_DAYS)
+    }
+
+    /// Check if the recurrence matches the date, the business days being the ones that are not
+    /// `weekend_days`
+    #[must_use]
+    #[allow(clippy::missing_panics_doc)]
+    pub fn matches_with_weekend_days(&self, date: NaiveDate, weekend_days: &[Weekday]) -> bool {
         match self {
             Self::Daily => true,
             Self::Weekly(weekdays) => weekdays.contains(&date.weekday()),
@@ -52,33 +103,113 @@ impl Recurrence {
                 yeardays.contains(&Yearday::try_from(date.ordinal()).unwrap())
             }
             Self::Once(dates) => dates.contains(&date),
+            Self::BusinessMonthly(index) => {
+                is_business_day(date, weekend_days)
+                    && date
+                        .with_day(1)
+                        .unwrap()
+                        .iter_days()
+                        .take_while(|day| *day <= date)
+                        .filter(|day| is_business_day(*day, weekend_days))
+                        .count()
+                        == *index as usize
+            }
 
-            Self::RelativeMonthly(weekdays, index) => {
+            Self::RelativeMonthly(weekdays, indices) => {
                 if weekdays.contains(&date.weekday()) {
                     let monthday0 = date.day0();
                     let week_index = monthday0 / 7;
                     let month = Month::from(date);
                     let from_last_index = (month.num_days() - date.day()) / 7;
 
-                    match index {
+                    indices.iter().any(|index| match index {
                         WeekIndex::First => week_index == 0,
                         WeekIndex::Second => week_index == 1,
                         WeekIndex::Third => week_index == 2,
                         WeekIndex::Fourth => week_index == 3,
                         WeekIndex::Last => from_last_index == 0,
-                    }
+                    })
                 } else {
                     false
                 }
             }
         }
     }
+
+    /// Frequency of the recurrence, as written in the events
+    #[must_use]
+    pub const fn frequency(&self) -> Frequency {
+        match self {
+            Self::Daily => Frequency::Daily,
+            Self::Weekly(_) => Frequency::Weekly,
+            Self::Monthly(_) | Self::RelativeMonthly(..) | Self::BusinessMonthly(_) => {
+                Frequency::Monthly
+            }
+            Self::Yearly(_) => Frequency::Yearly,
+            Self::Once(_) => Frequency::Once,
+        }
+    }
+
+    /// Check if the recurrence only occurs on a finite list of dates, i.e. only `Once`
+    #[must_use]
+    pub const fn is_bounded(&self) -> bool {
+        matches!(self, Self::Once(_))
+    }
+
+    /// Check if the recurrence matches any date between `from` and `to`, both inclusive
+    #[must_use]
+    pub fn matches_range(&self, from: NaiveDate, to: NaiveDate) -> bool {
+        from.iter_days()
+            .take_while(|date| *date <= to)
+            .any(|date| self.matches(date))
+    }
+
+    /// Human readable summary, e.g. "Every first Monday of the month"
+    #[must_use]
+    pub fn describe(&self) -> String {
+        fn join<T>(items: &[T], to_string: impl Fn(&T) -> String) -> String {
+            items.iter().map(to_string).collect::<Vec<_>>().join(", ")
+        }
+
+        match self {
+            Self::Daily => "Every day".to_owned(),
+            Self::Weekly(weekdays) => {
+                format!("Weekly on {}", join(weekdays, Weekday::to_string))
+            }
+            Self::Monthly(monthdays) => {
+                format!("Monthly on day {}", join(monthdays, Monthday::to_string))
+            }
+            Self::RelativeMonthly(weekdays, indices) => format!(
+                "Every {} {} of the month",
+                join(indices, |index| index.name().to_owned()),
+                join(weekdays, |weekday| weekday_name(*weekday).to_owned())
+            ),
+            Self::Yearly(yeardays) => {
+                format!("Yearly on day {}", join(yeardays, Yearday::to_string))
+            }
+            Self::BusinessMonthly(index) => format!("Monthly on business day {index}"),
+            Self::Once(dates) => format!("Once on {}", join(dates, NaiveDate::to_string)),
+        }
+    }
+}
+
+fn is_business_day(date: NaiveDate, weekend_days: &[Weekday]) -> bool {
+    !weekend_days.contains(&date.weekday())
+}
+
+/// The events are always written in English
+const fn weekday_name(weekday: Weekday) -> &'static str {
+    crate::locale::weekday_name(weekday, Locale::English)
 }
 
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct SerdeRecurrence {
     frequency: Frequency,
-    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[serde(
+        default,
+        skip_serializing_if = "Vec::is_empty",
+        serialize_with = "serialize_weekdays"
+    )]
     weekdays: Vec<Weekday>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     monthdays: Vec<u32>,
@@ -86,7 +217,45 @@ pub struct SerdeRecurrence {
     yeardays: Vec<u32>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     dates: Vec<NaiveDate>,
-    index: Option<WeekIndex>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    index: Option<WeekIndices>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    business_day: Option<u32>,
+}
+
+/// Write the weekdays with their full name, e.g. `Sunday` rather than `Sun`, as in the events
+fn serialize_weekdays<S>(weekdays: &[Weekday], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.collect_seq(weekdays.iter().map(|weekday| weekday_name(*weekday)))
+}
+
+/// Week indices of a relative monthly recurrence, a single one being written as is
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+enum WeekIndices {
+    One(WeekIndex),
+    Many(Vec<WeekIndex>),
+}
+
+impl From<WeekIndices> for Vec<WeekIndex> {
+    fn from(indices: WeekIndices) -> Self {
+        match indices {
+            WeekIndices::One(index) => vec![index],
+            WeekIndices::Many(indices) => indices,
+        }
+    }
+}
+
+impl From<Vec<WeekIndex>> for WeekIndices {
+    fn from(mut indices: Vec<WeekIndex>) -> Self {
+        if indices.len() == 1 {
+            Self::One(indices.remove(0))
+        } else {
+            Self::Many(indices)
+        }
+    }
 }
 
 #[derive(Debug, derive_more::From, derive_more::Display, derive_more::Error)]
@@ -97,8 +266,13 @@ pub enum InvalidRecurrence {
     WeekdaysRequired,
     #[display("`monthdays` not allowed")]
     MonthdaysNotAllowed,
-    #[display("`weekdays` or `monthdays` must be specified")]
+    #[display("`weekdays`, `monthdays` or `business_day` must be specified")]
     WeekdaysOrMonthdaysRequired,
+    #[display("`business_day` not allowed")]
+    BusinessDayNotAllowed,
+    #[display("Invalid `business_day` {_0}, expected between 1 and 23")]
+    #[from(ignore)]
+    InvalidBusinessDay(#[error(not(source))] u32),
     #[display("`yeardays` not allowed")]
     YeardaysNotAllowed,
     #[display("`yeardays` must be specified")]
@@ -116,9 +290,13 @@ pub enum InvalidRecurrence {
 impl TryFrom<SerdeRecurrence> for Recurrence {
     type Error = InvalidRecurrence;
 
+    #[allow(clippy::too_many_lines)]
     fn try_from(serde: SerdeRecurrence) -> Result<Self, Self::Error> {
         Ok(match serde.frequency {
             Frequency::Daily => {
+                if serde.business_day.is_some() {
+                    return Err(InvalidRecurrence::BusinessDayNotAllowed);
+                }
                 if !serde.weekdays.is_empty() {
                     return Err(InvalidRecurrence::WeekdaysNotAllowed);
                 }
@@ -134,6 +312,9 @@ impl TryFrom<SerdeRecurrence> for Recurrence {
                 Self::Daily
             }
             Frequency::Weekly => {
+                if serde.business_day.is_some() {
+                    return Err(InvalidRecurrence::BusinessDayNotAllowed);
+                }
                 if !serde.monthdays.is_empty() {
                     return Err(InvalidRecurrence::MonthdaysNotAllowed);
                 }
@@ -155,7 +336,18 @@ impl TryFrom<SerdeRecurrence> for Recurrence {
                 if !serde.dates.is_empty() {
                     return Err(InvalidRecurrence::DatesNotAllowed);
                 }
-                if serde.weekdays.is_empty() {
+                if let Some(index) = serde.business_day {
+                    if !serde.weekdays.is_empty() {
+                        return Err(InvalidRecurrence::WeekdaysNotAllowed);
+                    }
+                    if !serde.monthdays.is_empty() {
+                        return Err(InvalidRecurrence::MonthdaysNotAllowed);
+                    }
+                    if !(1..=23).contains(&index) {
+                        return Err(InvalidRecurrence::InvalidBusinessDay(index));
+                    }
+                    Self::BusinessMonthly(index)
+                } else if serde.weekdays.is_empty() {
                     if serde.monthdays.is_empty() {
                         return Err(InvalidRecurrence::WeekdaysOrMonthdaysRequired);
                     }
@@ -167,10 +359,17 @@ impl TryFrom<SerdeRecurrence> for Recurrence {
                             .collect::<Result<Vec<_>, InvalidMonthday>>()?,
                     )
                 } else {
-                    Self::RelativeMonthly(serde.weekdays, serde.index.unwrap_or(WeekIndex::First))
+                    let mut indices = serde.index.map(Vec::from).unwrap_or_default();
+                    if indices.is_empty() {
+                        indices.push(WeekIndex::First);
+                    }
+                    Self::RelativeMonthly(serde.weekdays, indices)
                 }
             }
             Frequency::Yearly => {
+                if serde.business_day.is_some() {
+                    return Err(InvalidRecurrence::BusinessDayNotAllowed);
+                }
                 if !serde.weekdays.is_empty() {
                     return Err(InvalidRecurrence::WeekdaysNotAllowed);
                 }
@@ -192,6 +391,9 @@ impl TryFrom<SerdeRecurrence> for Recurrence {
                 )
             }
             Frequency::Once => {
+                if serde.business_day.is_some() {
+                    return Err(InvalidRecurrence::BusinessDayNotAllowed);
+                }
                 if !serde.weekdays.is_empty() {
                     return Err(InvalidRecurrence::WeekdaysNotAllowed);
                 }
@@ -224,18 +426,23 @@ impl From<Recurrence> for SerdeRecurrence {
             },
             Recurrence::Monthly(monthdays) => Self {
                 frequency: Frequency::Monthly,
-                monthdays: monthdays.into_iter().map(u32::from).collect(),
+                monthdays: monthdays.into_iter().map(Monthday::get).collect(),
                 ..Default::default()
             },
-            Recurrence::RelativeMonthly(weekdays, index) => Self {
+            Recurrence::RelativeMonthly(weekdays, indices) => Self {
                 frequency: Frequency::Monthly,
                 weekdays,
-                index: Some(index),
+                index: Some(indices.into()),
+                ..Default::default()
+            },
+            Recurrence::BusinessMonthly(index) => Self {
+                frequency: Frequency::Monthly,
+                business_day: Some(index),
                 ..Default::default()
             },
             Recurrence::Yearly(yeardays) => Self {
                 frequency: Frequency::Yearly,
-                yeardays: yeardays.into_iter().map(u32::from).collect(),
+                yeardays: yeardays.into_iter().map(Yearday::get).collect(),
                 ..Default::default()
             },
             Recurrence::Once(dates) => Self {
@@ -287,34 +494,36 @@ mod tests {
         );
 
         assert!(
-            !RelativeMonthly(vec![Mon], First)
+            !RelativeMonthly(vec![Mon], vec![First])
                 .matches(NaiveDate::from_ymd_opt(2026, 2, 1).unwrap())
         );
         assert!(
-            RelativeMonthly(vec![Sun], First).matches(NaiveDate::from_ymd_opt(2026, 2, 1).unwrap())
+            RelativeMonthly(vec![Sun], vec![First])
+                .matches(NaiveDate::from_ymd_opt(2026, 2, 1).unwrap())
         );
         assert!(
-            RelativeMonthly(vec![Sun, Mon], First)
+            RelativeMonthly(vec![Sun, Mon], vec![First])
                 .matches(NaiveDate::from_ymd_opt(2026, 2, 2).unwrap())
         );
         assert!(
-            !RelativeMonthly(vec![Sun, Mon], First)
+            !RelativeMonthly(vec![Sun, Mon], vec![First])
                 .matches(NaiveDate::from_ymd_opt(2026, 2, 8).unwrap())
         );
         assert!(
-            RelativeMonthly(vec![Sun, Mon], Second)
+            RelativeMonthly(vec![Sun, Mon], vec![Second])
                 .matches(NaiveDate::from_ymd_opt(2026, 2, 8).unwrap())
         );
         assert!(
-            !RelativeMonthly(vec![Sun, Mon], Third)
+            !RelativeMonthly(vec![Sun, Mon], vec![Third])
                 .matches(NaiveDate::from_ymd_opt(2026, 2, 2).unwrap())
         );
         assert!(
-            RelativeMonthly(vec![Sun], Fourth)
+            RelativeMonthly(vec![Sun], vec![Fourth])
                 .matches(NaiveDate::from_ymd_opt(2026, 2, 22).unwrap())
         );
         assert!(
-            RelativeMonthly(vec![Sun], Last).matches(NaiveDate::from_ymd_opt(2026, 2, 22).unwrap())
+            RelativeMonthly(vec![Sun], vec![Last])
+                .matches(NaiveDate::from_ymd_opt(2026, 2, 22).unwrap())
         );
 
         assert!(Yearly(vec![yearday(32)]).matches(NaiveDate::from_ymd_opt(2026, 2, 1).unwrap()));
@@ -341,6 +550,97 @@ mod tests {
         );
     }
 
+    #[test]
+    fn is_bounded() {
+        let date = NaiveDate::from_ymd_opt(2026, 2, 1).unwrap();
+        assert!(Recurrence::Once(vec![date]).is_bounded());
+        assert!(!Recurrence::Daily.is_bounded());
+        assert!(!Recurrence::Weekly(vec![Weekday::Mon]).is_bounded());
+        assert!(!Recurrence::BusinessMonthly(1).is_bounded());
+    }
+
+    #[test]
+    fn recurrence_matches_range() {
+        use Recurrence::*;
+
+        let yearly = Yearly(vec![yearday(32)]);
+        assert!(yearly.matches_range(date(2026, 2, 1), date(2026, 2, 28)));
+        assert!(yearly.matches_range(date(2026, 1, 1), date(2026, 12, 31)));
+        assert!(yearly.matches_range(date(2026, 2, 1), date(2026, 2, 1)));
+        assert!(!yearly.matches_range(date(2026, 2, 2), date(2026, 2, 28)));
+        assert!(!yearly.matches_range(date(2026, 3, 1), date(2026, 1, 1)));
+
+        let once = Once(vec![date(2026, 2, 3)]);
+        assert!(once.matches_range(date(2026, 1, 1), date(2026, 12, 31)));
+        assert!(!once.matches_range(date(2025, 1, 1), date(2025, 12, 31)));
+    }
+
+    #[test]
+    fn recurrence_describe() {
+        use Recurrence::*;
+        use WeekIndex::*;
+        use Weekday::*;
+
+        assert_eq!("Every day", Daily.describe());
+        assert_eq!("Weekly on Mon, Wed", Weekly(vec![Mon, Wed]).describe());
+        assert_eq!(
+            "Monthly on day 1, 15",
+            Monthly(vec![monthday(1), monthday(15)]).describe()
+        );
+        assert_eq!(
+            "Every first Monday of the month",
+            RelativeMonthly(vec![Mon], vec![First]).describe()
+        );
+        assert_eq!(
+            "Every second Monday of the month",
+            RelativeMonthly(vec![Mon], vec![Second]).describe()
+        );
+        assert_eq!(
+            "Every third Tuesday, Thursday of the month",
+            RelativeMonthly(vec![Tue, Thu], vec![Third]).describe()
+        );
+        assert_eq!(
+            "Every fourth Saturday of the month",
+            RelativeMonthly(vec![Sat], vec![Fourth]).describe()
+        );
+        assert_eq!(
+            "Every last Friday, Sunday of the month",
+            RelativeMonthly(vec![Fri, Sun], vec![Last]).describe()
+        );
+        assert_eq!("Monthly on business day 3", BusinessMonthly(3).describe());
+        assert_eq!("Yearly on day 74", Yearly(vec![yearday(74)]).describe());
+        assert_eq!(
+            "Once on 2026-02-03, 2026-03-01",
+            Once(vec![date(2026, 2, 3), date(2026, 3, 1)]).describe()
+        );
+    }
+
+    #[test]
+    fn business_monthly_matches() {
+        let third = Recurrence::BusinessMonthly(3);
+
+        // April 2026 starts on a Wednesday
+        assert!(third.matches(date(2026, 4, 3)));
+        assert!(!third.matches(date(2026, 4, 6)));
+
+        // August 2026 starts on a Saturday
+        assert!(!third.matches(date(2026, 8, 3)));
+        assert!(third.matches(date(2026, 8, 5)));
+        assert!(!third.matches(date(2026, 8, 8)));
+
+        let first = Recurrence::BusinessMonthly(1);
+        assert!(!first.matches(date(2026, 8, 1)));
+        assert!(!first.matches(date(2026, 8, 2)));
+        assert!(first.matches(date(2026, 8, 3)));
+
+        // With Friday and Saturday weekends, August 2026 starts on a weekend day then a Sunday
+        let weekend_days = [Weekday::Fri, Weekday::Sat];
+        assert!(!first.matches_with_weekend_days(date(2026, 8, 1), &weekend_days));
+        assert!(first.matches_with_weekend_days(date(2026, 8, 2), &weekend_days));
+        assert!(third.matches_with_weekend_days(date(2026, 8, 4), &weekend_days));
+        assert!(!third.matches_with_weekend_days(date(2026, 8, 5), &weekend_days));
+    }
+
     mod daily {
         use super::*;
 
@@ -415,6 +715,36 @@ mod tests {
             assert_eq!(Recurrence::Weekly(vec![Weekday::Mon]), event.recurrence);
         }
 
+        #[test]
+        fn weekly_short_weekdays() {
+            let event = assert_ok!(Event::try_from(&CodeBlock::toml(
+                r#"
+                frequency = "weekly"
+                weekdays = ["mon", "Tue", "WED", "thursday", "FRIDAY"]
+                content = "Weekly"
+            "#,
+            )));
+
+            assert_eq!(
+                Recurrence::Weekly(vec![
+                    Weekday::Mon,
+                    Weekday::Tue,
+                    Weekday::Wed,
+                    Weekday::Thu,
+                    Weekday::Fri
+                ]),
+                event.recurrence
+            );
+
+            assert_err!(Event::try_from(&CodeBlock::toml(
+                r#"
+                frequency = "weekly"
+                weekdays = ["mo"]
+                content = "Weekly"
+            "#,
+            )));
+        }
+
         #[test]
         fn weekly_empty_weekdays() {
             assert_err!(Event::try_from(&CodeBlock::toml(
@@ -462,6 +792,18 @@ mod tests {
     mod monthly {
         use super::*;
 
+        #[test]
+        fn monthly_round_trip() {
+            let recurrence = Recurrence::Monthly(vec![monthday(1), monthday(15), monthday(31)]);
+
+            let serialized =
+                assert_ok!(toml::to_string(&SerdeRecurrence::from(recurrence.clone())));
+            assert!(serialized.contains("monthdays = [1, 15, 31]"));
+
+            let deserialized = assert_ok!(toml::from_str::<SerdeRecurrence>(&serialized));
+            assert_eq!(recurrence, assert_ok!(Recurrence::try_from(deserialized)));
+        }
+
         #[test]
         fn monthly_unspecified() {
             assert_err!(Event::try_from(&CodeBlock::toml(
@@ -483,7 +825,7 @@ mod tests {
             )));
 
             assert_eq!(
-                Recurrence::RelativeMonthly(vec![Weekday::Mon], WeekIndex::First),
+                Recurrence::RelativeMonthly(vec![Weekday::Mon], vec![WeekIndex::First]),
                 event.recurrence
             );
         }
@@ -500,11 +842,54 @@ mod tests {
             )));
 
             assert_eq!(
-                Recurrence::RelativeMonthly(vec![Weekday::Sun, Weekday::Fri], WeekIndex::Last),
+                Recurrence::RelativeMonthly(
+                    vec![Weekday::Sun, Weekday::Fri],
+                    vec![WeekIndex::Last]
+                ),
                 event.recurrence
             );
         }
 
+        #[test]
+        fn monthly_weekdays_indices() {
+            let event = assert_ok!(Event::try_from(&CodeBlock::toml(
+                r#"
+                frequency = "monthly"
+                weekdays = ["Wednesday"]
+                index = ["first", "third"]
+                content = "Weekly"
+            "#,
+            )));
+
+            let recurrence = Recurrence::RelativeMonthly(
+                vec![Weekday::Wed],
+                vec![WeekIndex::First, WeekIndex::Third],
+            );
+            assert_eq!(recurrence, event.recurrence);
+
+            // March 2026 starts on a Sunday
+            assert!(recurrence.matches(date(2026, 3, 4)));
+            assert!(!recurrence.matches(date(2026, 3, 11)));
+            assert!(recurrence.matches(date(2026, 3, 18)));
+            assert!(!recurrence.matches(date(2026, 3, 25)));
+            assert!(!recurrence.matches(date(2026, 3, 5)));
+            assert_eq!(
+                "Every first, third Wednesday of the month",
+                recurrence.describe()
+            );
+
+            let serialized =
+                assert_ok!(toml::to_string(&SerdeRecurrence::from(recurrence.clone())));
+            assert!(serialized.contains(r#"index = ["first", "third"]"#));
+            let deserialized = assert_ok!(toml::from_str::<SerdeRecurrence>(&serialized));
+            assert_eq!(recurrence, assert_ok!(Recurrence::try_from(deserialized)));
+
+            // A single index is still written as a string
+            let single = Recurrence::RelativeMonthly(vec![Weekday::Wed], vec![WeekIndex::Last]);
+            let serialized = assert_ok!(toml::to_string(&SerdeRecurrence::from(single)));
+            assert!(serialized.contains(r#"index = "last""#));
+        }
+
         #[test]
         fn monthly_monthdays() {
             let event = assert_ok!(Event::try_from(&CodeBlock::toml(
@@ -541,6 +926,71 @@ mod tests {
         }
     }
 
+    mod business_monthly {
+        use super::*;
+
+        #[test]
+        fn business_day() {
+            let event = assert_ok!(Event::try_from(&CodeBlock::toml(
+                r#"
+                frequency = "monthly"
+                business_day = 3
+                content = "Payroll"
+            "#,
+            )));
+
+            assert_eq!(Recurrence::BusinessMonthly(3), event.recurrence);
+        }
+
+        #[test]
+        fn business_day_out_of_range() {
+            for index in [0, 24] {
+                assert_err!(Event::try_from(&CodeBlock::toml(format!(
+                    r#"
+                    frequency = "monthly"
+                    business_day = {index}
+                    content = "Payroll"
+                "#
+                ))));
+            }
+        }
+
+        #[test]
+        fn business_day_weekdays() {
+            assert_err!(Event::try_from(&CodeBlock::toml(
+                r#"
+                frequency = "monthly"
+                business_day = 3
+                weekdays = ["Monday"]
+                content = "Payroll"
+            "#,
+            )));
+        }
+
+        #[test]
+        fn business_day_monthdays() {
+            assert_err!(Event::try_from(&CodeBlock::toml(
+                r#"
+                frequency = "monthly"
+                business_day = 3
+                monthdays = [1]
+                content = "Payroll"
+            "#,
+            )));
+        }
+
+        #[test]
+        fn business_day_not_monthly() {
+            assert_err!(Event::try_from(&CodeBlock::toml(
+                r#"
+                frequency = "daily"
+                business_day = 3
+                content = "Payroll"
+            "#,
+            )));
+        }
+    }
+
     mod yearly {
         use super::*;
 
@@ -1,5 +1,8 @@
+pub mod clock;
 pub mod content;
 pub mod date;
+pub mod error;
 pub mod events;
+pub mod locale;
 pub mod options;
 pub mod page;
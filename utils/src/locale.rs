@@ -0,0 +1,199 @@
+use chrono::{Datelike, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+
+/// Language of the generated text
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Locale {
+    #[default]
+    English,
+    French,
+}
+
+/// Age of someone, e.g. `25 years old`
+#[must_use]
+pub fn format_age(years: u32, locale: Locale) -> String {
+    match locale {
+        Locale::English if years == 1 => "1 year old".to_owned(),
+        Locale::English => format!("{years} years old"),
+        Locale::French if years <= 1 => format!("{years} an"),
+        Locale::French => format!("{years} ans"),
+    }
+}
+
+/// Long-form date, e.g. `Monday, 6 January 2025`
+#[must_use]
+pub fn long_date(date: NaiveDate, locale: Locale) -> String {
+    let weekday = weekday_name(date.weekday(), locale);
+    let month = month_name(date.month0(), locale);
+    match locale {
+        Locale::English => format!("{weekday}, {} {month} {}", date.day(), date.year()),
+        Locale::French => format!("{weekday} {} {month} {}", date.day(), date.year()),
+    }
+}
+
+/// Name of the day of the week, e.g. `Monday`
+#[must_use]
+pub const fn weekday_name(weekday: Weekday, locale: Locale) -> &'static str {
+    let names = match locale {
+        Locale::English => [
+            "Monday",
+            "Tuesday",
+            "Wednesday",
+            "Thursday",
+            "Friday",
+            "Saturday",
+            "Sunday",
+        ],
+        Locale::French => [
+            "lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi", "dimanche",
+        ],
+    };
+    names[weekday.num_days_from_monday() as usize]
+}
+
+/// Abbreviated name of the day of the week, e.g. `Mon`
+#[must_use]
+pub fn weekday_abbrev(weekday: Weekday, locale: Locale) -> &'static str {
+    let name = weekday_name(weekday, locale);
+    name.char_indices()
+        .nth(3)
+        .map_or(name, |(index, _)| &name[..index])
+}
+
+/// Label of the weeks in their page names, e.g. `Week` for `Week 02`
+#[must_use]
+pub const fn week_label(locale: Locale) -> &'static str {
+    match locale {
+        Locale::English => "Week",
+        Locale::French => "Semaine",
+    }
+}
+
+/// Label of the events summary, e.g. `Events`
+#[must_use]
+pub const fn events_label(locale: Locale) -> &'static str {
+    match locale {
+        Locale::English => "Events",
+        Locale::French => "Événements",
+    }
+}
+
+/// Relative names of the next and previous days, e.g. `Tomorrow` and `Yesterday`
+#[must_use]
+pub const fn relative_days(locale: Locale) -> (&'static str, &'static str) {
+    match locale {
+        Locale::English => ("Tomorrow", "Yesterday"),
+        Locale::French => ("Demain", "Hier"),
+    }
+}
+
+/// Name of the month, from 0 for January, e.g. `January`
+const fn month_name(month0: u32, locale: Locale) -> &'static str {
+    let names = match locale {
+        Locale::English => [
+            "January",
+            "February",
+            "March",
+            "April",
+            "May",
+            "June",
+            "July",
+            "August",
+            "September",
+            "October",
+            "November",
+            "December",
+        ],
+        Locale::French => [
+            "janvier",
+            "février",
+            "mars",
+            "avril",
+            "mai",
+            "juin",
+            "juillet",
+            "août",
+            "septembre",
+            "octobre",
+            "novembre",
+            "décembre",
+        ],
+    };
+    names[month0 as usize]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_age_english() {
+        assert_eq!("0 years old", format_age(0, Locale::English));
+        assert_eq!("1 year old", format_age(1, Locale::English));
+        assert_eq!("25 years old", format_age(25, Locale::English));
+    }
+
+    #[test]
+    fn format_age_french() {
+        assert_eq!("0 an", format_age(0, Locale::French));
+        assert_eq!("1 an", format_age(1, Locale::French));
+        assert_eq!("25 ans", format_age(25, Locale::French));
+    }
+
+    #[test]
+    fn relative_days_locales() {
+        assert_eq!(("Tomorrow", "Yesterday"), relative_days(Locale::default()));
+        assert_eq!(("Demain", "Hier"), relative_days(Locale::French));
+    }
+
+    #[test]
+    fn weekday_abbrev_locales() {
+        assert_eq!("Mon", weekday_abbrev(Weekday::Mon, Locale::default()));
+        assert_eq!("Sun", weekday_abbrev(Weekday::Sun, Locale::English));
+        assert_eq!("mer", weekday_abbrev(Weekday::Wed, Locale::French));
+        assert_eq!("dim", weekday_abbrev(Weekday::Sun, Locale::French));
+    }
+
+    #[test]
+    fn events_label_locales() {
+        assert_eq!("Events", events_label(Locale::default()));
+        assert_eq!("Événements", events_label(Locale::French));
+    }
+
+    #[test]
+    fn week_label_locales() {
+        assert_eq!("Week", week_label(Locale::default()));
+        assert_eq!("Semaine", week_label(Locale::French));
+    }
+
+    #[test]
+    fn long_date_english() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        assert_eq!(
+            "Monday, 6 January 2025",
+            long_date(date(2025, 1, 6), Locale::default())
+        );
+        assert_eq!(
+            "Sunday, 31 December 2028",
+            long_date(date(2028, 12, 31), Locale::English)
+        );
+        assert_eq!(
+            date(2025, 1, 6).format("%A, %-d %B %Y").to_string(),
+            long_date(date(2025, 1, 6), Locale::English)
+        );
+    }
+
+    #[test]
+    fn long_date_french() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        assert_eq!(
+            "lundi 6 janvier 2025",
+            long_date(date(2025, 1, 6), Locale::French)
+        );
+        assert_eq!(
+            "vendredi 15 août 2025",
+            long_date(date(2025, 8, 15), Locale::French)
+        );
+    }
+}
@@ -1,22 +1,28 @@
+use crate::clock::{Clock, SystemClock};
+use crate::date::{Month, ToDateIterator, Year};
 use chrono::NaiveDate;
 use clap::Arg;
 use serde::{Deserialize, Serialize};
 use std::ffi::OsString;
 use std::path::PathBuf;
+use std::str::FromStr;
 
 pub mod day;
 pub mod month;
 pub mod week;
 pub mod year;
 
-pub trait GenericSettings: Default + PartialEq {
-    type Option: clap::ValueEnum + Clone + Send + Sync + 'static;
+pub trait GenericSettings: Default + PartialEq + Clone {
+    type Option: clap::ValueEnum + Clone + PartialEq + Send + Sync + 'static;
 
     fn is_empty(&self) -> bool {
         self == &Self::default()
     }
 
     fn to_options(&self) -> Vec<Self::Option>;
+
+    /// Union with the other settings, enabling every flag enabled in either of them
+    fn merge(&mut self, other: &Self);
 }
 
 pub trait GenericPage: Default + PartialEq {
@@ -52,8 +58,9 @@ pub trait GenericPage: Default + PartialEq {
             .join(" ");
 
         format!(
-            "{}\n\nUse --{} instead to disable.\n\n[default: {}]",
+            "{}\n\nPrefix a value with - to remove it from the defaults, e.g. -{}.\nUse --{} instead to disable.\n\n[default: {}]",
             Self::help(),
+            default_values.split(' ').next_back().unwrap_or_default(),
             Self::disabling_flag(),
             default_values
         )
@@ -66,27 +73,44 @@ pub trait GenericPage: Default + PartialEq {
     fn settings(&self) -> &Self::Settings;
     fn update(&mut self, settings: &Self::Settings);
 
+    /// Union the given settings with the current ones
+    ///
+    /// A page with default settings takes the given settings as-is, and a disabled page stays
+    /// disabled.
+    fn merge(&mut self, settings: &Self::Settings) {
+        if self.is_default() {
+            self.update(settings);
+        } else if !self.settings().is_empty() {
+            let mut merged = self.settings().clone();
+            merged.merge(settings);
+            self.update(&merged);
+        }
+    }
+
     #[must_use]
     fn flag() -> &'static str;
     #[must_use]
     fn disabling_flag() -> &'static str;
 
+    /// Short flag, the first character of the flag by default, to override when it is not ASCII
+    /// or clashes with another flag
     #[must_use]
     fn flag_short() -> Option<char> {
-        Self::flag().chars().next()
+        short_flag(Self::flag())
     }
 
     #[must_use]
     fn arg() -> Arg {
-        use clap::builder::EnumValueParser;
-
         Arg::new(Self::flag())
             .short(Self::flag_short())
             .long(Self::flag())
             .help(Self::help())
             .long_help(Self::default_long_help())
-            .value_parser(EnumValueParser::<<Self::Settings as GenericSettings>::Option>::new())
+            .value_parser(ToggleValueParser::<
+                <Self::Settings as GenericSettings>::Option,
+            >::new())
             .value_delimiter(',')
+            .allow_hyphen_values(true)
             .action(clap::ArgAction::Append)
     }
 
@@ -100,12 +124,78 @@ pub trait GenericPage: Default + PartialEq {
     }
 }
 
+/// First character of the flag, unless it is not ASCII
+fn short_flag(flag: &str) -> Option<char> {
+    flag.chars().next().filter(char::is_ascii)
+}
+
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, clap::ValueEnum)]
+pub enum LogFormat {
+    /// Human readable lines
+    #[default]
+    Text,
+    /// One JSON object per line
+    Json,
+}
+
+/// Period covered by a prepared page, e.g. to create it from a template or to only prepare it
+#[derive(Debug, Clone, Copy, Eq, PartialEq, clap::ValueEnum)]
+pub enum Period {
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+impl Period {
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Day => "day",
+            Self::Week => "week",
+            Self::Month => "month",
+            Self::Year => "year",
+        }
+    }
+}
+
+/// Parse a `<KIND>=<FILE>` page template
+fn page_template(string: &str) -> Result<(Period, PathBuf), String> {
+    use clap::ValueEnum;
+
+    let (kind, file) = string
+        .split_once('=')
+        .ok_or_else(|| format!("expected <KIND>=<FILE>, got {string:?}"))?;
+    let kind = Period::from_str(kind, true)?;
+    if file.is_empty() {
+        return Err(format!("expected a file after {kind:?}="));
+    }
+    Ok((kind, PathBuf::from(file)))
+}
+
 #[derive(Debug)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct Options {
     pub from: NaiveDate,
     pub to: NaiveDate,
     pub path: PathBuf,
+    pub init: bool,
+    pub clean: bool,
+    pub stats: bool,
+    pub force: bool,
+    pub skip_existing: bool,
+    pub since_modified: bool,
+    pub merge_config: bool,
+    pub exclude_weekends: bool,
+    pub no_properties: bool,
+    pub append_only: bool,
+    pub strict: bool,
+    pub preview: Option<NaiveDate>,
+    pub post_hook: Option<String>,
+    pub page_templates: Vec<(Period, PathBuf)>,
+    pub output_dir: Option<PathBuf>,
     pub log_level_filter: log::LevelFilter,
+    pub log_format: LogFormat,
     #[allow(clippy::struct_field_names)]
     pub page_options: PageOptions,
 }
@@ -158,6 +248,45 @@ impl PageOptions {
     }
 }
 
+impl PageOptions {
+    /// Union the settings with the current options, see `GenericPage::merge`
+    pub fn merge(&mut self, settings: &PageSettings) {
+        if let Some(day_settings) = settings.day.as_ref() {
+            self.day.merge(day_settings);
+        }
+
+        if let Some(week_settings) = settings.week.as_ref() {
+            self.week.merge(week_settings);
+        }
+
+        if let Some(month_settings) = settings.month.as_ref() {
+            self.month.merge(month_settings);
+        }
+
+        if let Some(year_settings) = settings.year.as_ref() {
+            self.year.merge(year_settings);
+        }
+    }
+}
+
+impl PageOptions {
+    /// Disable the pages of the kinds not listed
+    pub fn retain(&mut self, kinds: &[Period]) {
+        if !kinds.contains(&Period::Day) {
+            self.day = day::Page::disabled();
+        }
+        if !kinds.contains(&Period::Week) {
+            self.week = week::Page::disabled();
+        }
+        if !kinds.contains(&Period::Month) {
+            self.month = month::Page::disabled();
+        }
+        if !kinds.contains(&Period::Year) {
+            self.year = year::Page::disabled();
+        }
+    }
+}
+
 impl From<&clap::ArgMatches> for PageOptions {
     fn from(matches: &clap::ArgMatches) -> Self {
         Self {
@@ -169,6 +298,133 @@ impl From<&clap::ArgMatches> for PageOptions {
     }
 }
 
+/// Page option given on the command line, either enabled (e.g. `events`) or disabled when
+/// prefixed by `-` (e.g. `-events`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Toggle<O> {
+    Enable(O),
+    Disable(O),
+}
+
+impl<O: Clone + PartialEq> Toggle<O> {
+    /// Build the settings from the toggles given in order
+    ///
+    /// The enabled options are used as-is, unless an option is disabled in which case the
+    /// toggles apply to the default settings instead.
+    pub fn settings<'a, S>(toggles: impl Iterator<Item = &'a Self>, default: &S) -> S
+    where
+        O: 'a,
+        S: GenericSettings<Option = O> + for<'b> FromIterator<&'b O>,
+    {
+        let toggles = toggles.collect::<Vec<_>>();
+        let mut options = if toggles
+            .iter()
+            .any(|toggle| matches!(toggle, Self::Disable(_)))
+        {
+            default.to_options()
+        } else {
+            vec![]
+        };
+
+        for toggle in toggles {
+            match toggle {
+                Self::Enable(option) => {
+                    if !options.contains(option) {
+                        options.push(option.clone());
+                    }
+                }
+                Self::Disable(option) => options.retain(|o| o != option),
+            }
+        }
+
+        options.iter().collect()
+    }
+}
+
+/// Parse a `Toggle` of a `clap::ValueEnum`, listing the possible values of the enum in the help
+#[derive(Clone)]
+struct ToggleValueParser<O>(std::marker::PhantomData<O>);
+
+impl<O> ToggleValueParser<O> {
+    const fn new() -> Self {
+        Self(std::marker::PhantomData)
+    }
+}
+
+impl<O> clap::builder::TypedValueParser for ToggleValueParser<O>
+where
+    O: clap::ValueEnum + Clone + Send + Sync + 'static,
+{
+    type Value = Toggle<O>;
+
+    fn parse_ref(
+        &self,
+        cmd: &clap::Command,
+        arg: Option<&Arg>,
+        value: &std::ffi::OsStr,
+    ) -> Result<Self::Value, clap::Error> {
+        let parser = clap::builder::EnumValueParser::<O>::new();
+        let string = value.to_string_lossy();
+        string.strip_prefix('-').map_or_else(
+            || parser.parse_ref(cmd, arg, value).map(Toggle::Enable),
+            |name| {
+                parser
+                    .parse_ref(cmd, arg, std::ffi::OsStr::new(name))
+                    .map(Toggle::Disable)
+            },
+        )
+    }
+
+    fn possible_values(
+        &self,
+    ) -> Option<Box<dyn Iterator<Item = clap::builder::PossibleValue> + '_>> {
+        Some(Box::new(
+            O::value_variants()
+                .iter()
+                .filter_map(clap::ValueEnum::to_possible_value),
+        ))
+    }
+}
+
+/// Bound of the prepared range, either a single date or a whole month or year
+#[derive(Debug, Clone, Copy)]
+enum Bound {
+    Date(NaiveDate),
+    Month(Month),
+    Year(Year),
+}
+
+impl Bound {
+    fn first(self) -> NaiveDate {
+        match self {
+            Self::Date(date) => date,
+            Self::Month(month) => month.first(),
+            Self::Year(year) => year.first().first(),
+        }
+    }
+
+    fn last(self) -> NaiveDate {
+        match self {
+            Self::Date(date) => date,
+            Self::Month(month) => month.last(),
+            Self::Year(year) => year.last().last(),
+        }
+    }
+}
+
+impl FromStr for Bound {
+    type Err = String;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        string
+            .parse()
+            .map(Self::Date)
+            .or_else(|_| string.parse().map(Self::Month))
+            .or_else(|_| string.parse().map(Self::Year))
+            .map_err(|_| format!("expected a date, a month or a year, got {string:?}"))
+    }
+}
+
 /// Parse given arguments
 ///
 /// # Errors
@@ -177,38 +433,129 @@ pub fn parse<I, T>(args_iter: I) -> Result<Options, clap::error::Error>
 where
     I: IntoIterator<Item = T>,
     T: Into<OsString> + Clone,
+{
+    parse_with_clock(args_iter, &SystemClock)
+}
+
+/// Parse given arguments, the default dates being relative to the date of the clock
+///
+/// # Errors
+/// `clap::error::Error`: Error parsing arguments
+#[allow(clippy::too_many_lines)]
+pub fn parse_with_clock<I, T, C>(args_iter: I, clock: &C) -> Result<Options, clap::error::Error>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<OsString> + Clone,
+    C: Clock,
 {
     use clap::{arg, command, value_parser};
     use clap_verbosity_flag::{ErrorLevel, Verbosity};
 
     let from_help = "Only prepare journal start from given date";
-    let from_default = chrono::Utc::now().date_naive();
-    let from_long_help = format!("{from_help}\n\n[default: {from_default}]");
+    let from_default = clock.today();
+    let from_long_help = format!(
+        "{from_help}\n\nA month (e.g. 2026-03) or a year (e.g. 2026) starts on its first day.\n\n[default: {from_default}]"
+    );
 
     let to_help = "Only prepare journal start from given date";
-    let to_long_help = format!("{to_help}\n\n[default: 1 month after --from]");
+    let to_long_help = format!(
+        "{to_help}\n\nA month (e.g. 2026-03) or a year (e.g. 2026) ends on its last day.\n\n[default: 1 month after --from, or the end of the --from month or year]"
+    );
 
     let mut command = command!()
         .arg(arg!(verbose: -v --verbose ... "Increase logging verbosity"))
         .arg(arg!(quiet: -q --quiet ... "Decrease logging verbosity").conflicts_with("verbose"))
+        .arg(
+            arg!(log_format: --"log-format" <FORMAT> "Format of the logs, unless logging to journald")
+                .required(false)
+                .default_value("text")
+                .value_parser(value_parser!(LogFormat)),
+        )
         .arg(
             arg!(path: -p --path <PATH> "Path to notes")
                 .required(true)
                 .value_parser(value_parser!(std::path::PathBuf)),
         )
+        .arg(
+            arg!(output_dir: --"output-dir" <DIR> "Write the pages in this directory instead of the notes")
+                .long_help("Write the pages in this directory instead of the notes\n\nThe configuration and the events are still read from the notes.")
+                .required(false)
+                .value_parser(value_parser!(std::path::PathBuf)),
+        )
+        .arg(arg!(init: --init "Create the configuration and event files with examples, then exit"))
+        .arg(
+            arg!(clean: --clean "Remove the pages of the range instead of preparing them")
+                .long_help("Remove the pages of the range instead of preparing them\n\nOnly the pages containing nothing but the generated content are removed, unless --force is given."),
+        )
+        .arg(arg!(force: --force "With --clean, also remove the pages containing other content").requires("clean"))
+        .arg(
+            arg!(stats: --stats "Print statistics of the events of the range instead of preparing it")
+                .long_help("Print statistics of the events of the range instead of preparing it\n\nThe statistics are the number of days with events, the busiest day and the number of events per frequency.")
+                .conflicts_with("clean"),
+        )
+        .arg(arg!(skip_existing: --"skip-existing" "Do not modify pages that already exist"))
+        .arg(arg!(since_modified: --"since-modified" "Do not modify pages more recent than the configuration and event files"))
+        .arg(arg!(merge_config: --"merge-config" "Enable options from both the command line and the configuration file"))
+        .arg(arg!(exclude_weekends: --"exclude-weekends" "Do not prepare the day pages of the weekends, Saturdays and Sundays by default"))
+        .arg(arg!(no_properties: --"no-properties" "Do not add properties to the pages, only their content"))
+        .arg(
+            arg!(append_only: --"append-only" "Keep the content of existing pages, only appending the new lines")
+                .conflicts_with("clean"),
+        )
+        .arg(
+            arg!(strict: --strict "Fail when a configured event file or template does not exist")
+                .long_help("Fail when a configured event file or template does not exist\n\nThe event files that are missing or contain invalid events are otherwise skipped, and the templates only read when creating a page. The default event file is only required if configured."),
+        )
+        .arg(
+            arg!(preview: --preview <DATE> "Print the day page of the date instead of preparing the journal")
+                .long_help("Print the day page of the date instead of preparing the journal\n\nThe page is printed with its properties and events, as it would be written, without writing it.")
+                .required(false)
+                .value_parser(NaiveDate::from_str)
+                .conflicts_with_all([
+                    "from",
+                    "to",
+                    "clean",
+                    "stats",
+                    "skip_existing",
+                    "since_modified",
+                    day::Page::disabling_flag(),
+                ]),
+        )
+        .arg(
+            arg!(post_hook: --"post-hook" <COMMAND> "Shell command to run once all the pages are written")
+                .long_help("Shell command to run once all the pages are written\n\nThe number of written pages is available in PREPARER_CHANGED_FILES.")
+                .required(false),
+        )
+        .arg(
+            arg!(page_template: --"page-template" <TEMPLATE> "Create the new pages of a kind from a template, as <KIND>=<FILE>")
+                .long_help("Create the new pages of a kind from a template, as <KIND>=<FILE>\n\nThe kind is day, week, month or year. In the template, {date} is replaced by the first day of the page, and {date:<format>} by the first day with the given format.")
+                .required(false)
+                .action(clap::ArgAction::Append)
+                .value_parser(page_template),
+        )
+        .arg(
+            arg!(only: --only <KIND> "Only prepare the pages of these kinds")
+                .long_help("Only prepare the pages of these kinds\n\nThe kind is day, week, month or year. The pages of the other kinds are not updated, as with --no-<KIND>-page.")
+                .required(false)
+                .action(clap::ArgAction::Append)
+                .value_delimiter(',')
+                .value_parser(value_parser!(Period)),
+        )
         .arg(
             arg!(from: --from <DATE>)
+                .visible_alias("since")
                 .help(from_help)
                 .long_help(from_long_help)
                 .required(false)
-                .value_parser(value_parser!(NaiveDate)),
+                .value_parser(Bound::from_str),
         )
         .arg(
             arg!(to: --to <DATE> "Only prepare journal up to given date")
+                .visible_alias("until")
                 .help(to_help)
                 .long_help(to_long_help)
                 .required(false)
-                .value_parser(value_parser!(NaiveDate)),
+                .value_parser(Bound::from_str),
         )
         .arg(day::Page::arg())
         .arg(day::Page::disabling_arg())
@@ -221,14 +568,13 @@ where
 
     let matches = command.try_get_matches_from_mut(args_iter)?;
 
-    let from = matches
-        .get_one::<NaiveDate>("from")
-        .copied()
-        .unwrap_or(from_default);
-    let to = matches
-        .get_one::<NaiveDate>("to")
-        .copied()
-        .unwrap_or(from + chrono::Months::new(1));
+    let from_bound = matches.get_one::<Bound>("from").copied();
+    let from = from_bound.map_or(from_default, Bound::first);
+    let to = match (matches.get_one::<Bound>("to").copied(), from_bound) {
+        (Some(to), _) => to.last(),
+        (None, Some(period @ (Bound::Month(_) | Bound::Year(_)))) => period.last(),
+        (None, _) => from + chrono::Months::new(1),
+    };
 
     if to < from {
         return Err(command.error(
@@ -237,24 +583,87 @@ where
         ));
     }
 
-    let page_options = PageOptions::from(&matches);
+    let mut page_options = PageOptions::from(&matches);
+    if let Some(only) = matches.get_many::<Period>("only") {
+        let only = only.copied().collect::<Vec<_>>();
+        let disabled = [
+            (Period::Day, day::Page::disabling_flag()),
+            (Period::Week, week::Page::disabling_flag()),
+            (Period::Month, month::Page::disabling_flag()),
+            (Period::Year, year::Page::disabling_flag()),
+        ];
+        for (kind, flag) in disabled {
+            if only.contains(&kind) && matches.get_flag(flag) {
+                return Err(command.error(
+                    clap::error::ErrorKind::ArgumentConflict,
+                    format!("--only {} cannot be used with --{flag}", kind.name()),
+                ));
+            }
+        }
+        if !only.contains(&Period::Day) && matches.contains_id("preview") {
+            return Err(command.error(
+                clap::error::ErrorKind::ArgumentConflict,
+                "--preview prints a day page and cannot be used with --only without day",
+            ));
+        }
+        page_options.retain(&only);
+    }
 
     let path = matches
         .get_one::<std::path::PathBuf>("path")
         .unwrap_or_else(|| unreachable!("'PATH' is required and parsing will fail if its missing"))
         .clone();
 
+    let init = matches.get_flag("init");
+    let clean = matches.get_flag("clean");
+    let stats = matches.get_flag("stats");
+    let force = matches.get_flag("force");
+    let skip_existing = matches.get_flag("skip_existing");
+    let since_modified = matches.get_flag("since_modified");
+    let merge_config = matches.get_flag("merge_config");
+    let exclude_weekends = matches.get_flag("exclude_weekends");
+    let no_properties = matches.get_flag("no_properties");
+    let append_only = matches.get_flag("append_only");
+    let strict = matches.get_flag("strict");
+    let preview = matches.get_one::<NaiveDate>("preview").copied();
+    let post_hook = matches.get_one::<String>("post_hook").cloned();
+    let output_dir = matches.get_one::<PathBuf>("output_dir").cloned();
+    let page_templates = matches
+        .get_many::<(Period, PathBuf)>("page_template")
+        .map(|templates| templates.cloned().collect())
+        .unwrap_or_default();
+
     let log_level_filter = Verbosity::<ErrorLevel>::new(
         matches.get_one::<u8>("verbose").copied().unwrap_or(0u8),
         matches.get_one::<u8>("quiet").copied().unwrap_or(0u8),
     )
     .log_level_filter();
+    let log_format = matches
+        .get_one::<LogFormat>("log_format")
+        .copied()
+        .unwrap_or_default();
 
     Ok(Options {
         from,
         to,
         path,
+        init,
+        clean,
+        stats,
+        force,
+        skip_existing,
+        since_modified,
+        merge_config,
+        exclude_weekends,
+        no_properties,
+        append_only,
+        strict,
+        preview,
+        post_hook,
+        page_templates,
+        output_dir,
         log_level_filter,
+        log_format,
         page_options,
     })
 }
@@ -320,12 +729,338 @@ mod tests {
         parsed_cmd_err!(["-q", "-v"]);
     }
 
+    #[test]
+    fn flag_short() {
+        assert_eq!(Some('d'), day::Page::flag_short());
+        assert_eq!(Some('w'), week::Page::flag_short());
+        assert_eq!(Some('m'), month::Page::flag_short());
+        assert_eq!(Some('y'), year::Page::flag_short());
+
+        assert_eq!(Some('j'), short_flag("jour"));
+        assert_eq!(None, short_flag("été"));
+        assert_eq!(None, short_flag("日"));
+        assert_eq!(None, short_flag(""));
+    }
+
+    #[test]
+    fn log_format() {
+        assert_eq!(LogFormat::Text, parsed_cmd_ok!([]).log_format);
+        assert_eq!(
+            LogFormat::Text,
+            parsed_cmd_ok!(["--log-format", "text"]).log_format
+        );
+        assert_eq!(
+            LogFormat::Json,
+            parsed_cmd_ok!(["--log-format", "json"]).log_format
+        );
+        parsed_cmd_err!(["--log-format", "xml"]);
+    }
+
     #[test]
     fn from_after_to() {
         parsed_cmd_err!(["--from", "2025-12-31", "--to", "2025-01-01"]);
         parsed_cmd_ok!(["--from", "2025-01-01", "--to", "2025-12-31"]);
     }
 
+    #[test]
+    fn since_until() {
+        let Options { from, to, .. } =
+            parsed_cmd_ok!(["--since", "2025-01-01", "--until", "2025-12-31"]);
+        assert_eq!(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(), from);
+        assert_eq!(NaiveDate::from_ymd_opt(2025, 12, 31).unwrap(), to);
+
+        let Options { from, to, .. } = parsed_cmd_ok!(["--since", "2025-02"]);
+        assert_eq!(NaiveDate::from_ymd_opt(2025, 2, 1).unwrap(), from);
+        assert_eq!(NaiveDate::from_ymd_opt(2025, 2, 28).unwrap(), to);
+
+        let error = parsed_cmd_err!(["--since", "2025-12-31", "--until", "2025-01-01"]);
+        assert_eq!(clap::error::ErrorKind::ArgumentConflict, error.kind());
+        assert!(
+            error
+                .to_string()
+                .contains("--from 2025-12-31 should be less than --to 2025-01-01")
+        );
+
+        parsed_cmd_err!(["--from", "2025-01-01", "--since", "2025-01-01"]);
+    }
+
+    #[test]
+    fn from_to_year() {
+        let Options { from, to, .. } = parsed_cmd_ok!(["--from", "2026", "--to", "2026"]);
+        assert_eq!(NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(), from);
+        assert_eq!(NaiveDate::from_ymd_opt(2026, 12, 31).unwrap(), to);
+
+        let Options { from, to, .. } = parsed_cmd_ok!(["--from", "2026"]);
+        assert_eq!(NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(), from);
+        assert_eq!(NaiveDate::from_ymd_opt(2026, 12, 31).unwrap(), to);
+    }
+
+    #[test]
+    fn from_to_month() {
+        let Options { from, to, .. } = parsed_cmd_ok!(["--from", "2026-02"]);
+        assert_eq!(NaiveDate::from_ymd_opt(2026, 2, 1).unwrap(), from);
+        assert_eq!(NaiveDate::from_ymd_opt(2026, 2, 28).unwrap(), to);
+
+        let Options { from, to, .. } =
+            parsed_cmd_ok!(["--from", "2026-02-14", "--to", "2026/March"]);
+        assert_eq!(NaiveDate::from_ymd_opt(2026, 2, 14).unwrap(), from);
+        assert_eq!(NaiveDate::from_ymd_opt(2026, 3, 31).unwrap(), to);
+    }
+
+    #[test]
+    fn from_date_defaults_to_one_month() {
+        let Options { from, to, .. } = parsed_cmd_ok!(["--from", "2026-02-14"]);
+        assert_eq!(NaiveDate::from_ymd_opt(2026, 2, 14).unwrap(), from);
+        assert_eq!(NaiveDate::from_ymd_opt(2026, 3, 14).unwrap(), to);
+
+        parsed_cmd_err!(["--from", "foo"]);
+    }
+
+    #[test]
+    fn defaults_to_today() {
+        use crate::clock::FixedClock;
+
+        let today = NaiveDate::from_ymd_opt(2025, 1, 31).unwrap();
+        let Options { from, to, .. } = claim::assert_ok!(parse_with_clock(
+            ["binary_name", "--path", "."],
+            &FixedClock(today)
+        ));
+        assert_eq!(today, from);
+        assert_eq!(NaiveDate::from_ymd_opt(2025, 2, 28).unwrap(), to);
+
+        let Options { from, to, .. } = claim::assert_ok!(parse_with_clock(
+            ["binary_name", "--path", ".", "--to", "2025-03"],
+            &FixedClock(today)
+        ));
+        assert_eq!(today, from);
+        assert_eq!(NaiveDate::from_ymd_opt(2025, 3, 31).unwrap(), to);
+    }
+
+    #[test]
+    fn only() {
+        let Options { page_options, .. } = parsed_cmd_ok!(["--only", "week,month"]);
+        assert!(page_options.day.settings().is_empty());
+        assert!(page_options.week.is_default());
+        assert!(page_options.month.is_default());
+        assert!(page_options.year.settings().is_empty());
+
+        let Options { page_options, .. } =
+            parsed_cmd_ok!(["--only", "day", "--only", "year", "--day", "nav"]);
+        assert!(page_options.day.settings().nav_link);
+        assert!(!page_options.day.settings().events);
+        assert!(page_options.week.settings().is_empty());
+        assert!(page_options.month.settings().is_empty());
+        assert!(page_options.year.is_default());
+
+        parsed_cmd_err!(["--only", "foo"]);
+    }
+
+    #[test]
+    fn only_conflicts_with_disabled_page() {
+        parsed_cmd_err!(["--only", "month", "--no-month-page"]);
+        parsed_cmd_err!(["--only", "day,week", "--no-week-page"]);
+        parsed_cmd_ok!(["--only", "month", "--no-week-page"]);
+        parsed_cmd_ok!(["--only", "day,week", "--no-month-page", "--no-year-page"]);
+    }
+
+    #[test]
+    fn page_templates() {
+        assert!(parsed_cmd_ok!([]).page_templates.is_empty());
+        assert_eq!(
+            vec![
+                (Period::Day, PathBuf::from("templates/day.md")),
+                (Period::Week, PathBuf::from("week=.md")),
+            ],
+            parsed_cmd_ok!([
+                "--page-template",
+                "day=templates/day.md",
+                "--page-template",
+                "Week=week=.md"
+            ])
+            .page_templates
+        );
+
+        parsed_cmd_err!(["--page-template", "templates/day.md"]);
+        parsed_cmd_err!(["--page-template", "quarter=templates/quarter.md"]);
+        parsed_cmd_err!(["--page-template", "day="]);
+    }
+
+    #[test]
+    fn output_dir() {
+        assert_eq!(None, parsed_cmd_ok!([]).output_dir);
+        assert_eq!(
+            Some(PathBuf::from("staging")),
+            parsed_cmd_ok!(["--output-dir", "staging"]).output_dir
+        );
+        parsed_cmd_err!(["--output-dir"]);
+    }
+
+    #[test]
+    fn skip_existing() {
+        assert!(!parsed_cmd_ok!([]).skip_existing);
+        assert!(parsed_cmd_ok!(["--skip-existing"]).skip_existing);
+    }
+
+    #[test]
+    fn merge_page_options_day_with_flags() {
+        let Options {
+            mut page_options,
+            merge_config,
+            ..
+        } = parsed_cmd_ok!(["--day", "nav", "--merge-config"]);
+        assert!(merge_config);
+
+        let page_settings = PageSettings {
+            day: Some(day::Settings {
+                events: true,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        page_options.merge(&page_settings);
+        assert!(page_options.day.settings().nav_link);
+        assert!(page_options.day.settings().events);
+        assert!(!page_options.day.settings().day_of_week);
+    }
+
+    #[test]
+    fn update_page_options_day_with_flags_overrides() {
+        let Options {
+            mut page_options,
+            merge_config,
+            ..
+        } = parsed_cmd_ok!(["--day", "nav"]);
+        assert!(!merge_config);
+
+        let page_settings = PageSettings {
+            day: Some(day::Settings {
+                events: true,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        page_options.update(&page_settings);
+        assert!(page_options.day.settings().nav_link);
+        assert!(!page_options.day.settings().events);
+    }
+
+    #[test]
+    fn merge_page_options_day_without_flags() {
+        let mut page_options = PageOptions::default();
+        let page_settings = PageSettings {
+            day: Some(day::Settings {
+                events: true,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        page_options.merge(&page_settings);
+        assert!(!page_options.day.is_default());
+        assert!(page_options.day.settings().events);
+        assert!(!page_options.day.settings().nav_link);
+    }
+
+    #[test]
+    fn merge_page_options_day_does_not_override_disabling_flag() {
+        let Options {
+            mut page_options, ..
+        } = parsed_cmd_ok!(["--no-day-page", "--merge-config"]);
+
+        let page_settings = PageSettings {
+            day: Some(day::Settings {
+                events: true,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        page_options.merge(&page_settings);
+        assert!(page_options.day.settings().is_empty());
+    }
+
+    #[test]
+    fn post_hook() {
+        assert!(parsed_cmd_ok!([]).post_hook.is_none());
+        assert_eq!(
+            Some("git commit -a".to_owned()),
+            parsed_cmd_ok!(["--post-hook", "git commit -a"]).post_hook
+        );
+    }
+
+    #[test]
+    fn init() {
+        assert!(!parsed_cmd_ok!([]).init);
+        assert!(parsed_cmd_ok!(["--init"]).init);
+    }
+
+    #[test]
+    fn clean() {
+        let options = parsed_cmd_ok!([]);
+        assert!(!options.clean);
+        assert!(!options.force);
+
+        let options = parsed_cmd_ok!(["--clean"]);
+        assert!(options.clean);
+        assert!(!options.force);
+
+        let options = parsed_cmd_ok!(["--clean", "--force"]);
+        assert!(options.clean);
+        assert!(options.force);
+
+        parsed_cmd_err!(["--force"]);
+    }
+
+    #[test]
+    fn exclude_weekends() {
+        assert!(!parsed_cmd_ok!([]).exclude_weekends);
+        assert!(parsed_cmd_ok!(["--exclude-weekends"]).exclude_weekends);
+    }
+
+    #[test]
+    fn no_properties() {
+        assert!(!parsed_cmd_ok!([]).no_properties);
+        assert!(parsed_cmd_ok!(["--no-properties"]).no_properties);
+    }
+
+    #[test]
+    fn append_only() {
+        assert!(!parsed_cmd_ok!([]).append_only);
+        assert!(parsed_cmd_ok!(["--append-only"]).append_only);
+        parsed_cmd_err!(["--append-only", "--clean"]);
+    }
+
+    #[test]
+    fn strict() {
+        assert!(!parsed_cmd_ok!([]).strict);
+        assert!(parsed_cmd_ok!(["--strict"]).strict);
+    }
+
+    #[test]
+    fn preview() {
+        assert_eq!(None, parsed_cmd_ok!([]).preview);
+        assert_eq!(
+            NaiveDate::from_ymd_opt(2025, 12, 25),
+            parsed_cmd_ok!(["--preview", "2025-12-25"]).preview
+        );
+        parsed_cmd_err!(["--preview", "2025-12"]);
+        parsed_cmd_err!(["--preview", "2025-12-25", "--from", "2025-12-01"]);
+        parsed_cmd_err!(["--preview", "2025-12-25", "--clean"]);
+        parsed_cmd_err!(["--preview", "2025-12-25", "--skip-existing"]);
+        parsed_cmd_err!(["--preview", "2025-12-25", "--since-modified"]);
+        parsed_cmd_err!(["--preview", "2025-12-25", "--no-day-page"]);
+        parsed_cmd_err!(["--preview", "2025-12-25", "--only", "week"]);
+        parsed_cmd_ok!(["--preview", "2025-12-25", "--only", "day,week"]);
+    }
+
+    #[test]
+    fn since_modified() {
+        assert!(!parsed_cmd_ok!([]).since_modified);
+        assert!(parsed_cmd_ok!(["--since-modified"]).since_modified);
+    }
+
     #[test]
     fn update_page_options_day_does_not_override_flags() {
         let Options {
@@ -1,19 +1,35 @@
-use crate::options::{GenericPage, GenericSettings};
+use crate::options::{GenericPage, GenericSettings, Toggle};
 use clap::ValueEnum;
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, ValueEnum)]
+/// Type of the callout wrapping the events, e.g. `> [!note]-`
+pub const DEFAULT_CALLOUT_TYPE: &str = "note";
+
+/// Title of the callout wrapping the events
+pub const DEFAULT_CALLOUT_TITLE: &str = "Events";
+
+#[derive(Clone, Debug, PartialEq, Eq, ValueEnum)]
 pub enum Option {
     /// Add property day of week
     Day,
     /// Add property link to week
     Week,
+    /// Add property ISO week number
+    WeekNumber,
+    /// Add property day of the year, from 1 to 366
+    DayOfYear,
     /// Add property link to month
     Month,
     /// Add property links to previous and next day
     Nav,
     /// Add recurring events content, from events/recurring.md
     Events,
+    /// Add property aliases with the long-form date
+    Aliases,
+    /// Wrap the events in a collapsible callout, between markers
+    Callout,
+    /// Add the events between markers, to update them in place
+    Markers,
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -31,11 +47,61 @@ pub struct Settings {
     #[serde(default)]
     pub link_to_week: bool,
     #[serde(default)]
+    pub week_number: bool,
+    #[serde(default)]
+    pub day_of_year: bool,
+    #[serde(default)]
     pub link_to_month: bool,
     #[serde(default)]
     pub nav_link: bool,
     #[serde(default)]
     pub events: bool,
+    /// Only add events with at least one of these tags, all events if empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub aliases: bool,
+    /// Format of the alias, see `chrono::format::strftime`, the long-form date in the locale of
+    /// the vault if unset
+    #[serde(default, skip_serializing_if = "std::option::Option::is_none")]
+    pub alias_format: std::option::Option<String>,
+    #[serde(default)]
+    pub nav_title: NavTitle,
+    #[serde(default)]
+    pub callout: bool,
+    #[serde(default)]
+    pub markers: bool,
+    /// Type of the callout, e.g. `note` or `todo`
+    #[serde(default, skip_serializing_if = "std::option::Option::is_none")]
+    pub callout_type: std::option::Option<String>,
+    /// Title of the callout
+    #[serde(default, skip_serializing_if = "std::option::Option::is_none")]
+    pub callout_title: std::option::Option<String>,
+}
+
+/// Visible title of the links to the previous and next day
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NavTitle {
+    /// Date of the day, e.g. `2025-01-07`
+    #[default]
+    Date,
+    /// Relative label, i.e. `Yesterday` and `Tomorrow` in the configured locale
+    Relative,
+}
+
+impl Settings {
+    #[must_use]
+    pub fn callout_type(&self) -> &str {
+        self.callout_type.as_deref().unwrap_or(DEFAULT_CALLOUT_TYPE)
+    }
+
+    #[must_use]
+    pub fn callout_title(&self) -> &str {
+        self.callout_title
+            .as_deref()
+            .unwrap_or(DEFAULT_CALLOUT_TITLE)
+    }
 }
 
 impl GenericSettings for Settings {
@@ -49,6 +115,12 @@ impl GenericSettings for Settings {
         if self.link_to_week {
             options.push(Option::Week);
         }
+        if self.week_number {
+            options.push(Option::WeekNumber);
+        }
+        if self.day_of_year {
+            options.push(Option::DayOfYear);
+        }
         if self.link_to_month {
             options.push(Option::Month);
         }
@@ -58,8 +130,46 @@ impl GenericSettings for Settings {
         if self.events {
             options.push(Option::Events);
         }
+        if self.aliases {
+            options.push(Option::Aliases);
+        }
+        if self.callout {
+            options.push(Option::Callout);
+        }
+        if self.markers {
+            options.push(Option::Markers);
+        }
         options
     }
+
+    fn merge(&mut self, other: &Self) {
+        self.day_of_week |= other.day_of_week;
+        self.link_to_week |= other.link_to_week;
+        self.week_number |= other.week_number;
+        self.day_of_year |= other.day_of_year;
+        self.link_to_month |= other.link_to_month;
+        self.nav_link |= other.nav_link;
+        self.events |= other.events;
+        self.aliases |= other.aliases;
+        if self.alias_format.is_none() {
+            self.alias_format.clone_from(&other.alias_format);
+        }
+        self.callout |= other.callout;
+        self.markers |= other.markers;
+        if self.callout_type.is_none() {
+            self.callout_type.clone_from(&other.callout_type);
+        }
+        if self.callout_title.is_none() {
+            self.callout_title.clone_from(&other.callout_title);
+        }
+        // Not available on the command line, so the other settings are authoritative
+        self.nav_title = other.nav_title;
+        for tag in &other.tags {
+            if !self.tags.contains(tag) {
+                self.tags.push(tag.clone());
+            }
+        }
+    }
 }
 
 impl<'a> FromIterator<&'a Option> for Settings {
@@ -72,9 +182,14 @@ impl<'a> FromIterator<&'a Option> for Settings {
             match option {
                 Option::Day => settings.day_of_week = true,
                 Option::Week => settings.link_to_week = true,
+                Option::WeekNumber => settings.week_number = true,
+                Option::DayOfYear => settings.day_of_year = true,
                 Option::Month => settings.link_to_month = true,
                 Option::Nav => settings.nav_link = true,
                 Option::Events => settings.events = true,
+                Option::Aliases => settings.aliases = true,
+                Option::Callout => settings.callout = true,
+                Option::Markers => settings.markers = true,
             }
         }
         settings
@@ -87,10 +202,10 @@ impl From<&clap::ArgMatches> for Page {
             Self::disabled()
         } else {
             matches
-                .get_many::<Option>(Self::flag())
+                .get_many::<Toggle<Option>>(Self::flag())
                 .map(|options| Self {
                     default: false,
-                    settings: options.collect(),
+                    settings: Toggle::settings(options, Self::default().settings()),
                 })
                 .unwrap_or_default()
         }
@@ -104,9 +219,19 @@ impl Default for Page {
             settings: Settings {
                 day_of_week: true,
                 link_to_week: true,
+                week_number: false,
+                day_of_year: false,
                 link_to_month: true,
                 nav_link: true,
                 events: true,
+                tags: vec![],
+                aliases: false,
+                alias_format: None,
+                nav_title: NavTitle::Date,
+                callout: false,
+                markers: false,
+                callout_type: None,
+                callout_title: None,
             },
         }
     }
@@ -167,6 +292,60 @@ mod tests {
         assert!(!page.settings().events);
     }
 
+    #[test]
+    fn flag_day_negation() {
+        let Options {
+            page_options: PageOptions { day: page, .. },
+            ..
+        } = parsed_cmd_ok!(["--day", "-events"]);
+
+        assert!(!page.default);
+        assert!(page.settings().day_of_week);
+        assert!(page.settings().link_to_week);
+        assert!(page.settings().link_to_month);
+        assert!(page.settings().nav_link);
+        assert!(!page.settings().events);
+
+        let Options {
+            page_options: PageOptions { day: page, .. },
+            ..
+        } = parsed_cmd_ok!(["--day", "-events,-nav,aliases", "-d", "-day"]);
+
+        assert!(!page.settings().day_of_week);
+        assert!(page.settings().link_to_week);
+        assert!(page.settings().link_to_month);
+        assert!(!page.settings().nav_link);
+        assert!(!page.settings().events);
+        assert!(page.settings().aliases);
+
+        parsed_cmd_err!(["--day", "-foo"]);
+    }
+
+    #[test]
+    fn nav_title() {
+        let settings: Settings = toml::from_str("").unwrap();
+        assert_eq!(NavTitle::Date, settings.nav_title);
+
+        let settings: Settings = toml::from_str(r#"nav_title = "relative""#).unwrap();
+        assert_eq!(NavTitle::Relative, settings.nav_title);
+
+        assert!(toml::from_str::<Settings>(r#"nav_title = "foo""#).is_err());
+    }
+
+    #[test]
+    fn callout() {
+        let settings: Settings = toml::from_str("callout = true").unwrap();
+        assert!(settings.callout);
+        assert_eq!(DEFAULT_CALLOUT_TYPE, settings.callout_type());
+        assert_eq!(DEFAULT_CALLOUT_TITLE, settings.callout_title());
+
+        let settings: Settings =
+            toml::from_str("callout_type = \"todo\"\ncallout_title = \"Today\"").unwrap();
+        assert!(!settings.callout);
+        assert_eq!("todo", settings.callout_type());
+        assert_eq!("Today", settings.callout_title());
+    }
+
     #[test]
     fn flag_day_nav() {
         let Options {
@@ -1,13 +1,21 @@
-use crate::options::{GenericPage, GenericSettings};
+use crate::options::{GenericPage, GenericSettings, Toggle};
 use clap::ValueEnum;
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, ValueEnum)]
+#[derive(Clone, Debug, PartialEq, Eq, ValueEnum)]
 pub enum Option {
     /// Add embedded month days
     Month,
     /// Add property links to previous and next month
     Nav,
+    /// Add summary of the events of the month
+    Events,
+    /// Link to the month days instead of embedding them
+    Links,
+    /// Add embedded weeks of the month instead of its days
+    Weeks,
+    /// Only list the month days between --from and --to
+    Range,
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -17,11 +25,77 @@ pub struct Page {
 }
 
 #[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+// The flags are non-exclusive so we really need a bool
+#[allow(clippy::struct_excessive_bools)]
 pub struct Settings {
     #[serde(default)]
     pub month: bool,
     #[serde(default)]
     pub nav_link: bool,
+    #[serde(default)]
+    pub events: bool,
+    #[serde(default)]
+    pub link_days: bool,
+    #[serde(default)]
+    pub weeks: bool,
+    #[serde(default)]
+    pub days_in_range: bool,
+    #[serde(default)]
+    pub week_heading_level: HeadingLevel,
+    #[serde(default)]
+    pub day_label: DayLabel,
+}
+
+/// Label of the month days, before their link
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DayLabel {
+    /// Full name of the day of the week, e.g. `Monday`
+    #[default]
+    Full,
+    /// Three letters abbreviation of the day of the week, e.g. `Mon`
+    Abbrev,
+    /// No label, only the link
+    None,
+}
+
+/// Markdown heading level, from 1 to 6, displayed as the matching number of `#`
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "u8", into = "u8")]
+pub struct HeadingLevel(u8);
+
+#[derive(Debug, derive_more::Display, derive_more::Error)]
+#[display("Invalid heading level {_0}, expected 1 to 6")]
+pub struct InvalidHeadingLevel(#[error(ignore)] u8);
+
+impl Default for HeadingLevel {
+    fn default() -> Self {
+        Self(4)
+    }
+}
+
+impl TryFrom<u8> for HeadingLevel {
+    type Error = InvalidHeadingLevel;
+
+    fn try_from(level: u8) -> Result<Self, Self::Error> {
+        if (1..=6).contains(&level) {
+            Ok(Self(level))
+        } else {
+            Err(InvalidHeadingLevel(level))
+        }
+    }
+}
+
+impl From<HeadingLevel> for u8 {
+    fn from(level: HeadingLevel) -> Self {
+        level.0
+    }
+}
+
+impl std::fmt::Display for HeadingLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", "#".repeat(self.0.into()))
+    }
 }
 
 impl GenericSettings for Settings {
@@ -35,8 +109,32 @@ impl GenericSettings for Settings {
         if self.nav_link {
             options.push(Option::Nav);
         }
+        if self.events {
+            options.push(Option::Events);
+        }
+        if self.link_days {
+            options.push(Option::Links);
+        }
+        if self.weeks {
+            options.push(Option::Weeks);
+        }
+        if self.days_in_range {
+            options.push(Option::Range);
+        }
         options
     }
+
+    fn merge(&mut self, other: &Self) {
+        self.month |= other.month;
+        self.nav_link |= other.nav_link;
+        self.events |= other.events;
+        self.link_days |= other.link_days;
+        self.weeks |= other.weeks;
+        self.days_in_range |= other.days_in_range;
+        // Not available on the command line, so the other settings are authoritative
+        self.week_heading_level = other.week_heading_level;
+        self.day_label = other.day_label;
+    }
 }
 
 impl<'a> FromIterator<&'a Option> for Settings {
@@ -49,6 +147,10 @@ impl<'a> FromIterator<&'a Option> for Settings {
             match option {
                 Option::Month => settings.month = true,
                 Option::Nav => settings.nav_link = true,
+                Option::Events => settings.events = true,
+                Option::Links => settings.link_days = true,
+                Option::Weeks => settings.weeks = true,
+                Option::Range => settings.days_in_range = true,
             }
         }
         settings
@@ -61,10 +163,10 @@ impl From<&clap::ArgMatches> for Page {
             Self::disabled()
         } else {
             matches
-                .get_many::<Option>(Self::flag())
+                .get_many::<Toggle<Option>>(Self::flag())
                 .map(|options| Self {
                     default: false,
-                    settings: options.collect(),
+                    settings: Toggle::settings(options, Self::default().settings()),
                 })
                 .unwrap_or_default()
         }
@@ -78,6 +180,12 @@ impl Default for Page {
             settings: Settings {
                 month: true,
                 nav_link: true,
+                events: false,
+                link_days: false,
+                weeks: false,
+                days_in_range: false,
+                week_heading_level: HeadingLevel::default(),
+                day_label: DayLabel::default(),
             },
         }
     }
@@ -123,6 +231,37 @@ mod tests {
     use crate::options::tests::{parsed_cmd_err, parsed_cmd_ok};
     use crate::options::{Options, PageOptions};
 
+    #[test]
+    fn week_heading_level() {
+        let settings: Settings = toml::from_str("month = true").unwrap();
+        assert_eq!(HeadingLevel(4), settings.week_heading_level);
+        assert_eq!("####", settings.week_heading_level.to_string());
+
+        let settings: Settings = toml::from_str("week_heading_level = 2").unwrap();
+        assert_eq!(HeadingLevel(2), settings.week_heading_level);
+        assert_eq!("##", settings.week_heading_level.to_string());
+
+        assert!(toml::from_str::<Settings>("week_heading_level = 0").is_err());
+        assert!(toml::from_str::<Settings>("week_heading_level = 7").is_err());
+    }
+
+    #[test]
+    fn day_label() {
+        let settings: Settings = toml::from_str("month = true").unwrap();
+        assert_eq!(DayLabel::Full, settings.day_label);
+
+        for (value, label) in [
+            ("full", DayLabel::Full),
+            ("abbrev", DayLabel::Abbrev),
+            ("none", DayLabel::None),
+        ] {
+            let settings: Settings = toml::from_str(&format!("day_label = \"{value}\"")).unwrap();
+            assert_eq!(label, settings.day_label);
+        }
+
+        assert!(toml::from_str::<Settings>("day_label = \"short\"").is_err());
+    }
+
     #[test]
     fn flag_month_nav() {
         let Options {
@@ -133,6 +272,7 @@ mod tests {
         assert!(!page.default);
         assert!(!page.settings().month);
         assert!(page.settings().nav_link);
+        assert!(!page.settings().events);
     }
 
     #[test]
@@ -145,6 +285,60 @@ mod tests {
         assert!(!page.default);
         assert!(page.settings().month);
         assert!(!page.settings().nav_link);
+        assert!(!page.settings().events);
+    }
+
+    #[test]
+    fn flag_month_events() {
+        let Options {
+            page_options: PageOptions { month: page, .. },
+            ..
+        } = parsed_cmd_ok!(["--month", "events"]);
+
+        assert!(!page.default);
+        assert!(!page.settings().month);
+        assert!(!page.settings().nav_link);
+        assert!(page.settings().events);
+    }
+
+    #[test]
+    fn flag_month_links() {
+        let Options {
+            page_options: PageOptions { month: page, .. },
+            ..
+        } = parsed_cmd_ok!(["--month", "month,links"]);
+
+        assert!(!page.default);
+        assert!(page.settings().month);
+        assert!(!page.settings().nav_link);
+        assert!(!page.settings().events);
+        assert!(page.settings().link_days);
+    }
+
+    #[test]
+    fn flag_month_weeks() {
+        let Options {
+            page_options: PageOptions { month: page, .. },
+            ..
+        } = parsed_cmd_ok!(["--month", "weeks"]);
+
+        assert!(!page.default);
+        assert!(!page.settings().month);
+        assert!(!page.settings().nav_link);
+        assert!(page.settings().weeks);
+    }
+
+    #[test]
+    fn flag_month_range() {
+        let Options {
+            page_options: PageOptions { month: page, .. },
+            ..
+        } = parsed_cmd_ok!(["--month", "month,range"]);
+
+        assert!(!page.default);
+        assert!(page.settings().month);
+        assert!(page.settings().days_in_range);
+        assert!(!page.settings().weeks);
     }
 
     #[test]
@@ -152,12 +346,13 @@ mod tests {
         let Options {
             page_options: PageOptions { month: page, .. },
             ..
-        } = parsed_cmd_ok!(["--month", "nav", "--month", "month"]);
+        } = parsed_cmd_ok!(["--month", "nav", "--month", "month", "--month", "events"]);
 
         assert!(!page.default);
         assert!(!page.is_default());
         assert!(page.settings().month);
         assert!(page.settings().nav_link);
+        assert!(page.settings().events);
     }
 
     #[test]
@@ -165,12 +360,13 @@ mod tests {
         let Options {
             page_options: PageOptions { month: page, .. },
             ..
-        } = parsed_cmd_ok!(["--month", "nav,month"]);
+        } = parsed_cmd_ok!(["--month", "nav,month,events"]);
 
         assert!(!page.default);
         assert!(!page.is_default());
         assert!(page.settings().month);
         assert!(page.settings().nav_link);
+        assert!(page.settings().events);
     }
 
     #[test]
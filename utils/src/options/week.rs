@@ -1,8 +1,12 @@
-use crate::options::{GenericPage, GenericSettings};
+use crate::options::{GenericPage, GenericSettings, Toggle};
+use chrono::Weekday;
 use clap::ValueEnum;
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, ValueEnum)]
+/// Text after the link of the days listed as tasks
+pub const DEFAULT_TASK_LABEL: &str = "review";
+
+#[derive(Clone, Debug, PartialEq, Eq, ValueEnum)]
 pub enum Option {
     /// Add embedded week days
     Week,
@@ -10,6 +14,10 @@ pub enum Option {
     Month,
     /// Add property links to previous and next week
     Nav,
+    /// Add property with the range of dates of the week
+    Dates,
+    /// Add week days as tasks, e.g. for a weekly review
+    Tasks,
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -19,6 +27,8 @@ pub struct Page {
 }
 
 #[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+// The flags are non-exclusive so we really need a bool
+#[allow(clippy::struct_excessive_bools)]
 pub struct Settings {
     #[serde(default)]
     pub week: bool,
@@ -26,6 +36,23 @@ pub struct Settings {
     pub link_to_month: bool,
     #[serde(default)]
     pub nav_link: bool,
+    #[serde(default)]
+    pub date_range: bool,
+    #[serde(default)]
+    pub tasks: bool,
+    /// Text after the link of the days listed as tasks
+    #[serde(default, skip_serializing_if = "std::option::Option::is_none")]
+    pub task_label: std::option::Option<String>,
+    /// Day listed first in the page, the first day of the week if not set
+    #[serde(default, skip_serializing_if = "std::option::Option::is_none")]
+    pub display_start: std::option::Option<Weekday>,
+}
+
+impl Settings {
+    #[must_use]
+    pub fn task_label(&self) -> &str {
+        self.task_label.as_deref().unwrap_or(DEFAULT_TASK_LABEL)
+    }
 }
 
 impl GenericSettings for Settings {
@@ -42,8 +69,28 @@ impl GenericSettings for Settings {
         if self.nav_link {
             options.push(Option::Nav);
         }
+        if self.date_range {
+            options.push(Option::Dates);
+        }
+        if self.tasks {
+            options.push(Option::Tasks);
+        }
         options
     }
+
+    fn merge(&mut self, other: &Self) {
+        self.week |= other.week;
+        self.link_to_month |= other.link_to_month;
+        self.nav_link |= other.nav_link;
+        self.date_range |= other.date_range;
+        self.tasks |= other.tasks;
+        if self.task_label.is_none() {
+            self.task_label.clone_from(&other.task_label);
+        }
+        if self.display_start.is_none() {
+            self.display_start = other.display_start;
+        }
+    }
 }
 
 impl<'a> FromIterator<&'a Option> for Settings {
@@ -57,6 +104,8 @@ impl<'a> FromIterator<&'a Option> for Settings {
                 Option::Week => settings.week = true,
                 Option::Month => settings.link_to_month = true,
                 Option::Nav => settings.nav_link = true,
+                Option::Dates => settings.date_range = true,
+                Option::Tasks => settings.tasks = true,
             }
         }
         settings
@@ -69,10 +118,10 @@ impl From<&clap::ArgMatches> for Page {
             Self::disabled()
         } else {
             matches
-                .get_many::<Option>(Self::flag())
+                .get_many::<Toggle<Option>>(Self::flag())
                 .map(|options| Self {
                     default: false,
-                    settings: options.collect(),
+                    settings: Toggle::settings(options, Self::default().settings()),
                 })
                 .unwrap_or_default()
         }
@@ -87,6 +136,10 @@ impl Default for Page {
                 week: true,
                 link_to_month: true,
                 nav_link: true,
+                date_range: false,
+                tasks: false,
+                task_label: None,
+                display_start: None,
             },
         }
     }
@@ -132,6 +185,26 @@ mod tests {
     use crate::options::tests::{parsed_cmd_err, parsed_cmd_ok};
     use crate::options::{Options, PageOptions};
 
+    #[test]
+    fn display_start() {
+        let settings: Settings = toml::from_str("").unwrap();
+        assert_eq!(None, settings.display_start);
+
+        let settings: Settings = toml::from_str(r#"display_start = "Sunday""#).unwrap();
+        assert_eq!(Some(Weekday::Sun), settings.display_start);
+    }
+
+    #[test]
+    fn task_label() {
+        let settings: Settings = toml::from_str("tasks = true").unwrap();
+        assert!(settings.tasks);
+        assert_eq!(DEFAULT_TASK_LABEL, settings.task_label());
+
+        let settings: Settings = toml::from_str(r#"task_label = "plan""#).unwrap();
+        assert!(!settings.tasks);
+        assert_eq!("plan", settings.task_label());
+    }
+
     #[test]
     fn flag_week_nav() {
         let Options {
@@ -143,6 +216,7 @@ mod tests {
         assert!(!page.settings().week);
         assert!(!page.settings().link_to_month);
         assert!(page.settings().nav_link);
+        assert!(!page.settings().date_range);
     }
 
     #[test]
@@ -156,6 +230,7 @@ mod tests {
         assert!(!page.settings().week);
         assert!(page.settings().link_to_month);
         assert!(!page.settings().nav_link);
+        assert!(!page.settings().date_range);
     }
 
     #[test]
@@ -169,6 +244,21 @@ mod tests {
         assert!(page.settings().week);
         assert!(!page.settings().link_to_month);
         assert!(!page.settings().nav_link);
+        assert!(!page.settings().date_range);
+    }
+
+    #[test]
+    fn flag_week_dates() {
+        let Options {
+            page_options: PageOptions { week: page, .. },
+            ..
+        } = parsed_cmd_ok!(["--week", "dates"]);
+
+        assert!(!page.default);
+        assert!(!page.settings().week);
+        assert!(!page.settings().link_to_month);
+        assert!(!page.settings().nav_link);
+        assert!(page.settings().date_range);
     }
 
     #[test]
@@ -176,13 +266,16 @@ mod tests {
         let Options {
             page_options: PageOptions { week: page, .. },
             ..
-        } = parsed_cmd_ok!(["--week", "nav", "--week", "month", "--week", "week"]);
+        } = parsed_cmd_ok!([
+            "--week", "nav", "--week", "month", "--week", "week", "--week", "dates",
+        ]);
 
         assert!(!page.default);
         assert!(!page.is_default());
         assert!(page.settings().week);
         assert!(page.settings().link_to_month);
         assert!(page.settings().nav_link);
+        assert!(page.settings().date_range);
     }
 
     #[test]
@@ -190,13 +283,14 @@ mod tests {
         let Options {
             page_options: PageOptions { week: page, .. },
             ..
-        } = parsed_cmd_ok!(["--week", "nav,month,week"]);
+        } = parsed_cmd_ok!(["--week", "nav,month,week,dates"]);
 
         assert!(!page.default);
         assert!(!page.is_default());
         assert!(page.settings().week);
         assert!(page.settings().link_to_month);
         assert!(page.settings().nav_link);
+        assert!(page.settings().date_range);
     }
 
     #[test]
@@ -1,13 +1,20 @@
-use crate::options::{GenericPage, GenericSettings};
+use crate::options::month::HeadingLevel;
+use crate::options::{GenericPage, GenericSettings, Toggle};
 use clap::ValueEnum;
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, ValueEnum)]
+#[derive(Clone, Debug, PartialEq, Eq, ValueEnum)]
 pub enum Option {
     /// Add link to months
     Month,
     /// Add property links to previous and next year
     Nav,
+    /// Add summary of the events of the year, grouped by month
+    Events,
+    /// Add links to months on a single line, with abbreviated names
+    Compact,
+    /// Add links to the weeks belonging to the year
+    Weeks,
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -17,11 +24,21 @@ pub struct Page {
 }
 
 #[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+// The flags are non-exclusive so we really need a bool
+#[allow(clippy::struct_excessive_bools)]
 pub struct Settings {
     #[serde(default)]
     pub month: bool,
     #[serde(default)]
     pub nav_link: bool,
+    #[serde(default)]
+    pub events: bool,
+    #[serde(default)]
+    pub compact: bool,
+    #[serde(default)]
+    pub weeks: bool,
+    #[serde(default)]
+    pub month_heading_level: HeadingLevel,
 }
 
 impl GenericSettings for Settings {
@@ -35,8 +52,27 @@ impl GenericSettings for Settings {
         if self.nav_link {
             options.push(Option::Nav);
         }
+        if self.events {
+            options.push(Option::Events);
+        }
+        if self.compact {
+            options.push(Option::Compact);
+        }
+        if self.weeks {
+            options.push(Option::Weeks);
+        }
         options
     }
+
+    fn merge(&mut self, other: &Self) {
+        self.month |= other.month;
+        self.nav_link |= other.nav_link;
+        self.events |= other.events;
+        self.compact |= other.compact;
+        self.weeks |= other.weeks;
+        // Not available on the command line, so the other settings are authoritative
+        self.month_heading_level = other.month_heading_level;
+    }
 }
 
 impl<'a> FromIterator<&'a Option> for Settings {
@@ -49,6 +85,9 @@ impl<'a> FromIterator<&'a Option> for Settings {
             match option {
                 Option::Month => settings.month = true,
                 Option::Nav => settings.nav_link = true,
+                Option::Events => settings.events = true,
+                Option::Compact => settings.compact = true,
+                Option::Weeks => settings.weeks = true,
             }
         }
         settings
@@ -61,10 +100,10 @@ impl From<&clap::ArgMatches> for Page {
             Self::disabled()
         } else {
             matches
-                .get_many::<Option>(Self::flag())
+                .get_many::<Toggle<Option>>(Self::flag())
                 .map(|options| Self {
                     default: false,
-                    settings: options.collect(),
+                    settings: Toggle::settings(options, Self::default().settings()),
                 })
                 .unwrap_or_default()
         }
@@ -78,6 +117,10 @@ impl Default for Page {
             settings: Settings {
                 month: true,
                 nav_link: true,
+                events: false,
+                compact: false,
+                weeks: false,
+                month_heading_level: HeadingLevel::default(),
             },
         }
     }
@@ -123,6 +166,17 @@ mod tests {
     use crate::options::tests::{parsed_cmd_err, parsed_cmd_ok};
     use crate::options::{Options, PageOptions};
 
+    #[test]
+    fn month_heading_level() {
+        let settings: Settings = toml::from_str("month = true").unwrap();
+        assert_eq!("####", settings.month_heading_level.to_string());
+
+        let settings: Settings = toml::from_str("month_heading_level = 2").unwrap();
+        assert_eq!("##", settings.month_heading_level.to_string());
+
+        assert!(toml::from_str::<Settings>("month_heading_level = 7").is_err());
+    }
+
     #[test]
     fn flag_year_nav() {
         let Options {
@@ -133,6 +187,7 @@ mod tests {
         assert!(!page.default);
         assert!(!page.settings().month);
         assert!(page.settings().nav_link);
+        assert!(!page.settings().events);
     }
 
     #[test]
@@ -145,6 +200,48 @@ mod tests {
         assert!(!page.default);
         assert!(page.settings().month);
         assert!(!page.settings().nav_link);
+        assert!(!page.settings().events);
+    }
+
+    #[test]
+    fn flag_year_events() {
+        let Options {
+            page_options: PageOptions { year: page, .. },
+            ..
+        } = parsed_cmd_ok!(["--year", "events"]);
+
+        assert!(!page.default);
+        assert!(!page.settings().month);
+        assert!(!page.settings().nav_link);
+        assert!(page.settings().events);
+    }
+
+    #[test]
+    fn flag_year_compact() {
+        let Options {
+            page_options: PageOptions { year: page, .. },
+            ..
+        } = parsed_cmd_ok!(["--year", "month,compact"]);
+
+        assert!(!page.default);
+        assert!(page.settings().month);
+        assert!(!page.settings().nav_link);
+        assert!(!page.settings().events);
+        assert!(page.settings().compact);
+    }
+
+    #[test]
+    fn flag_year_weeks() {
+        let Options {
+            page_options: PageOptions { year: page, .. },
+            ..
+        } = parsed_cmd_ok!(["--year", "weeks"]);
+
+        assert!(!page.default);
+        assert!(!page.settings().month);
+        assert!(!page.settings().nav_link);
+        assert!(!page.settings().events);
+        assert!(page.settings().weeks);
     }
 
     #[test]
@@ -152,12 +249,13 @@ mod tests {
         let Options {
             page_options: PageOptions { year: page, .. },
             ..
-        } = parsed_cmd_ok!(["--year", "nav", "--year", "month"]);
+        } = parsed_cmd_ok!(["--year", "nav", "--year", "month", "--year", "events"]);
 
         assert!(!page.default);
         assert!(!page.is_default());
         assert!(page.settings().month);
         assert!(page.settings().nav_link);
+        assert!(page.settings().events);
     }
 
     #[test]
@@ -165,12 +263,13 @@ mod tests {
         let Options {
             page_options: PageOptions { year: page, .. },
             ..
-        } = parsed_cmd_ok!(["--year", "nav,month"]);
+        } = parsed_cmd_ok!(["--year", "nav,month,events"]);
 
         assert!(!page.default);
         assert!(!page.is_default());
         assert!(page.settings().month);
         assert!(page.settings().nav_link);
+        assert!(page.settings().events);
     }
 
     #[test]
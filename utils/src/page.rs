@@ -1,15 +1,21 @@
-use crate::content::{Content, ContentError, Entry};
-use saphyr::YamlOwned;
+use crate::content::{Content, ContentError, DEFAULT_DELIMITER, Entry};
+use saphyr::{ScalarOwned, YamlOwned};
+use std::collections::VecDeque;
 use std::fmt::Display;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Page {
     path: PathBuf,
     exists: bool,
     modified: bool,
     content: Content,
+    separator: Option<String>,
+    /// Index of the entry after the lines prepended or found by `prepend_line`, where the
+    /// separator belongs
+    block_end: Option<usize>,
+    properties_disabled: bool,
 }
 
 #[derive(Debug, derive_more::Error, derive_more::Display)]
@@ -20,18 +26,84 @@ pub enum PageError {
     CreatingFile(std::io::Error, PathBuf),
     #[display("Error writing file {}: {_0}", _1.display())]
     WritingFile(std::io::Error, PathBuf),
+    #[display("Error renaming file to {}: {_0}", _1.display())]
+    RenamingFile(std::io::Error, PathBuf),
     #[display("Error reading file {}: {_0}", _1.display())]
     ReadingFile(std::io::Error, PathBuf),
+    #[display("Error removing file {}: {_0}", _1.display())]
+    RemovingFile(std::io::Error, PathBuf),
     ParsingContent(ContentError),
 }
 
 impl Page {
+    /// Page without content at the path, ignoring the file if it exists
+    #[must_use]
+    pub fn empty(path: PathBuf) -> Self {
+        Self {
+            path,
+            exists: false,
+            modified: false,
+            content: Content::default(),
+            separator: None,
+            block_end: None,
+            properties_disabled: false,
+        }
+    }
+
+    /// Read the page at the path, with the properties between two `delimiter` lines, or an empty
+    /// page if the file does not exist
+    ///
+    /// # Errors
+    /// - `ReadingFile`
+    /// - `ParsingContent`
+    pub fn read(path: PathBuf, delimiter: &str) -> Result<Self, PageError> {
+        if !path.exists() {
+            let mut page = Self::empty(path);
+            page.set_delimiter(delimiter);
+            return Ok(page);
+        }
+
+        let content =
+            std::fs::read_to_string(&path).map_err(|e| PageError::ReadingFile(e, path.clone()))?;
+        let content = Content::parse(&content, delimiter).map_err(PageError::ParsingContent)?;
+        Ok(Self {
+            path,
+            exists: true,
+            modified: false,
+            content,
+            separator: None,
+            block_end: None,
+            properties_disabled: false,
+        })
+    }
+
+    /// Line written before and after the properties
+    pub fn set_delimiter(&mut self, delimiter: &str) {
+        self.content.set_delimiter(delimiter);
+    }
+
+    /// Replace the content of the page by the text, e.g. of a template, the properties being
+    /// between the delimiter lines of the page
+    ///
+    /// # Errors
+    /// - `ParsingContent`
+    pub fn set_content(&mut self, text: &str) -> Result<(), PageError> {
+        self.content =
+            Content::parse(text, self.content.delimiter()).map_err(PageError::ParsingContent)?;
+        self.modified = true;
+        Ok(())
+    }
+
     /// Write the page to disk
     ///
+    /// The content is first written to a temporary file in the same directory, which is then
+    /// renamed over the target, so an interrupted write never leaves a truncated page behind.
+    ///
     /// # Errors
     /// - `CreatingDir`
     /// - `CreatingFile`
     /// - `WritingFile`
+    /// - `RenamingFile`
     pub fn write(&mut self) -> Result<(), PageError> {
         if let Some(parent) = self.path.parent()
             && !parent.exists()
@@ -40,10 +112,12 @@ impl Page {
                 .map_err(|e| PageError::CreatingDir(e, parent.to_path_buf()))?;
         }
 
-        let mut file = std::fs::File::create(&self.path)
-            .map_err(|e| PageError::CreatingFile(e, self.path.clone()))?;
-        write!(file, "{}", self.content)
-            .map_err(|e| PageError::WritingFile(e, self.path.clone()))?;
+        let tmp_path = self.tmp_path();
+        if let Err(e) = self.write_to(&tmp_path) {
+            // Best effort, the original error is more relevant
+            let _ = std::fs::remove_file(&tmp_path);
+            return Err(e);
+        }
 
         self.exists = true;
         self.modified = false;
@@ -51,10 +125,70 @@ impl Page {
         Ok(())
     }
 
+    fn tmp_path(&self) -> PathBuf {
+        let mut file_name = std::ffi::OsString::from(".");
+        if let Some(name) = self.path.file_name() {
+            file_name.push(name);
+        }
+        file_name.push(".tmp");
+        self.path.with_file_name(file_name)
+    }
+
+    fn write_to(&self, tmp_path: &Path) -> Result<(), PageError> {
+        let mut file = std::fs::File::create(tmp_path)
+            .map_err(|e| PageError::CreatingFile(e, tmp_path.to_path_buf()))?;
+        write!(file, "{}", self.content)
+            .and_then(|()| file.sync_all())
+            .map_err(|e| PageError::WritingFile(e, tmp_path.to_path_buf()))?;
+
+        if let Ok(metadata) = std::fs::metadata(&self.path) {
+            file.set_permissions(metadata.permissions())
+                .map_err(|e| PageError::WritingFile(e, tmp_path.to_path_buf()))?;
+        }
+
+        std::fs::rename(tmp_path, &self.path)
+            .map_err(|e| PageError::RenamingFile(e, self.path.clone()))
+    }
+
+    /// Remove the page from disk
+    ///
+    /// # Errors
+    /// - `RemovingFile`
+    pub fn remove(&mut self) -> Result<(), PageError> {
+        std::fs::remove_file(&self.path)
+            .map_err(|e| PageError::RemovingFile(e, self.path.clone()))?;
+        self.exists = false;
+        Ok(())
+    }
+
+    /// Whether both pages would be written with the same text
+    #[must_use]
+    pub fn same_content(&self, other: &Self) -> bool {
+        self.content.to_string() == other.content.to_string()
+    }
+
     pub fn entries(&self) -> impl Iterator<Item = &Entry> {
         self.content.entries.iter()
     }
 
+    /// Whether the given line is already in the page
+    #[must_use]
+    pub fn contains_line<L: Display>(&self, line: L) -> bool {
+        self.content.contains_entry(&Entry::Line(format!("{line}")))
+    }
+
+    /// Text of the page as written to disk, with the properties
+    #[must_use]
+    pub fn text(&self) -> String {
+        self.content.to_string()
+    }
+
+    /// Text of the page without the properties
+    #[must_use]
+    pub fn body_text(&self) -> String {
+        self.content.body_text()
+    }
+
     pub fn prepend_lines<I, L>(&mut self, lines: I)
     where
         I: IntoIterator<Item = L>,
@@ -66,24 +200,172 @@ impl Page {
         }
     }
 
+    /// Separate the prepended lines from the rest of the content with the given line
+    ///
+    /// The separator is only added once, right after the prepended lines, unless it already
+    /// follows them
+    pub fn set_separator<S: Into<String>>(&mut self, separator: S) {
+        self.separator = Some(separator.into());
+    }
+
     pub fn prepend_line<L: Display>(&mut self, line: L) {
         let entry = Entry::Line(format!("{line}"));
+        if let Some(index) = self.content.entries.iter().position(|e| *e == entry) {
+            // The lines are prepended from the last one, so it's the end of the block
+            self.block_end.get_or_insert(index + 1);
+            return;
+        }
+
+        self.content.prepend_unique_entry(entry);
+        let block_end = self.block_end.map_or(1, |end| end + 1);
+        self.block_end = Some(block_end);
+        if let Some(separator) = &self.separator {
+            let separator = Entry::Line(separator.clone());
+            if self.content.entries.get(block_end) != Some(&separator) {
+                self.content.entries.insert(block_end, separator);
+            }
+        }
+        self.modified = true;
+    }
 
-        if self.content.prepend_unique_entry(entry) {
+    /// Replace the lines between the `start` line and the next `end` line, or prepend them between
+    /// these lines if the page has no `start` line, unless there are no lines
+    pub fn replace_marked_lines<I, L>(&mut self, start: &str, end: &str, lines: I)
+    where
+        I: IntoIterator<Item = L>,
+        L: Display,
+    {
+        let start = Entry::Line(start.to_owned());
+        let end = Entry::Line(end.to_owned());
+        let mut block = vec![start.clone()];
+        block.extend(lines.into_iter().map(|line| Entry::Line(format!("{line}"))));
+        block.push(end.clone());
+
+        let entries = &self.content.entries;
+        let Some(first) = entries.iter().position(|entry| *entry == start) else {
+            if block.len() > 2 {
+                let block_end = block.len();
+                for entry in block.into_iter().rev() {
+                    self.content.entries.push_front(entry);
+                }
+                if let Some(separator) = &self.separator {
+                    let separator = Entry::Line(separator.clone());
+                    if self.content.entries.get(block_end) != Some(&separator) {
+                        self.content.entries.insert(block_end, separator);
+                    }
+                }
+                self.modified = true;
+            }
+            return;
+        };
+        // Without its end, only the start line is replaced
+        let last = entries
+            .iter()
+            .skip(first)
+            .position(|entry| *entry == end)
+            .map_or(first, |len| first + len);
+
+        if entries.range(first..=last).ne(block.iter()) {
+            let after = self.content.entries.split_off(last + 1);
+            self.content.entries.truncate(first);
+            self.content.entries.extend(block);
+            self.content.entries.extend(after);
             self.modified = true;
         }
     }
 
+    /// Replace the entries of the page, keeping its properties
+    pub fn set_body<I: IntoIterator<Item = Entry>>(&mut self, entries: I) {
+        let entries = entries.into_iter().collect::<VecDeque<_>>();
+        if self.content.entries != entries {
+            self.content.entries = entries;
+            self.modified = true;
+        }
+    }
+
+    /// Restore the body of the `original` page verbatim if it exists, only adding at its end the
+    /// entries of this page it does not contain yet
+    pub fn restore_body(&mut self, original: &Self) {
+        if !original.exists {
+            return;
+        }
+
+        let mut entries = original.content.entries.clone();
+        for entry in &self.content.entries {
+            if !entries.contains(entry) {
+                entries.push_back(entry.clone());
+            }
+        }
+        self.content.entries = entries;
+        self.modified = !self.same_content(original);
+    }
+
+    /// Add the entry at the end of the page
+    pub fn append_entry<E: Into<Entry>>(&mut self, entry: E) {
+        self.content.entries.push_back(entry.into());
+        self.modified = true;
+    }
+
+    /// Ignore the properties inserted from now on, keeping the existing ones
+    pub const fn disable_properties(&mut self) {
+        self.properties_disabled = true;
+    }
+
     pub fn insert_property<K, V>(&mut self, key: K, value: V)
     where
         K: Into<String>,
         V: Display,
     {
+        if self.properties_disabled {
+            return;
+        }
         if self.content.insert_property(key.into(), format!("{value}")) {
             self.modified = true;
         }
     }
 
+    pub fn insert_integer_property<K>(&mut self, key: K, value: i64)
+    where
+        K: Into<String>,
+    {
+        if self.properties_disabled {
+            return;
+        }
+        let value = YamlOwned::Value(ScalarOwned::Integer(value));
+        if self.content.insert_property_value(key.into(), value) {
+            self.modified = true;
+        }
+    }
+
+    /// Add the values to the sequence property, keeping the values already present
+    pub fn insert_sequence_property<K, I, V>(&mut self, key: K, values: I)
+    where
+        K: Into<String>,
+        I: IntoIterator<Item = V>,
+        V: Display,
+    {
+        if self.properties_disabled {
+            return;
+        }
+        let key = key.into();
+        let mut sequence = match self.content.get_property(&key) {
+            Some(YamlOwned::Sequence(sequence)) => sequence.clone(),
+            _ => vec![],
+        };
+        for value in values {
+            let value = YamlOwned::Value(ScalarOwned::String(format!("{value}")));
+            if !sequence.contains(&value) {
+                sequence.push(value);
+            }
+        }
+        if self
+            .content
+            .insert_property_value(key, YamlOwned::Sequence(sequence))
+        {
+            self.modified = true;
+        }
+    }
+
     #[must_use]
     pub fn get_property(&self, key: &str) -> Option<&YamlOwned> {
         self.content.get_property(key)
@@ -112,35 +394,16 @@ impl TryFrom<PathBuf> for Page {
     type Error = PageError;
 
     fn try_from(path: PathBuf) -> Result<Self, Self::Error> {
-        let page = if path.exists() {
-            let content = std::fs::read_to_string(&path)
-                .map_err(|e| PageError::ReadingFile(e, path.clone()))?
-                .parse()
-                .map_err(PageError::ParsingContent)?;
-            Self {
-                path,
-                exists: true,
-                modified: false,
-                content,
-            }
-        } else {
-            Self {
-                path,
-                exists: false,
-                modified: false,
-                content: Content::default(),
-            }
-        };
-
-        Ok(page)
+        Self::read(path, DEFAULT_DELIMITER)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::content::CodeBlock;
     use assert_fs::prelude::*;
-    use claim::assert_ok;
+    use claim::{assert_err, assert_ok};
     use indoc::{formatdoc, indoc};
 
     #[test]
@@ -179,6 +442,261 @@ mod tests {
         assert!(page.modified());
     }
 
+    #[test]
+    fn separator() {
+        let temp_dir = assert_ok!(assert_fs::TempDir::new());
+        let file = temp_dir.child("page.md");
+        assert_ok!(file.write_str("My notes\n"));
+
+        for _ in 0..2 {
+            let mut page = assert_ok!(Page::try_from(file.path()));
+            page.set_separator("***");
+            page.prepend_lines(["Hello", "World"]);
+            if page.modified() {
+                assert_ok!(page.write());
+            }
+        }
+
+        file.assert(indoc! {"
+            Hello
+            World
+            ***
+            My notes
+        "});
+
+        let mut page = assert_ok!(Page::try_from(file.path()));
+        page.set_separator("***");
+        page.prepend_lines(["Foo", "Hello", "World"]);
+        assert_ok!(page.write());
+        file.assert(indoc! {"
+            Foo
+            Hello
+            World
+            ***
+            My notes
+        "});
+    }
+
+    #[test]
+    fn separator_elsewhere_in_page() {
+        let temp_dir = assert_ok!(assert_fs::TempDir::new());
+        let file = temp_dir.child("page.md");
+        assert_ok!(file.write_str("My notes\n***\nMore notes\n"));
+
+        let mut page = assert_ok!(Page::try_from(file.path()));
+        page.set_separator("***");
+        page.prepend_lines(["Hello", "World"]);
+        assert_ok!(page.write());
+        file.assert(indoc! {"
+            Hello
+            World
+            ***
+            My notes
+            ***
+            More notes
+        "});
+    }
+
+    #[test]
+    fn same_content_and_remove() {
+        let temp_dir = assert_ok!(assert_fs::TempDir::new());
+        let file = temp_dir.child("page.md");
+        assert_ok!(file.write_str("---\nfoo: bar\n---\nHello\n"));
+
+        let mut page = assert_ok!(Page::try_from(file.path()));
+        let mut empty = Page::empty(file.path().to_path_buf());
+        assert!(!empty.exists());
+        assert!(!page.same_content(&empty));
+
+        empty.prepend_line("Hello");
+        empty.insert_property("foo", "bar");
+        assert!(page.same_content(&empty));
+
+        assert_ok!(page.remove());
+        assert!(!page.exists());
+        assert!(!file.path().exists());
+        assert_err!(page.remove());
+    }
+
+    #[test]
+    fn delimiter() {
+        let temp_dir = assert_ok!(assert_fs::TempDir::new());
+        let file = temp_dir.child("page.md");
+        let text = indoc! {r#"
+            +++
+            foo = "bar"
+            +++
+            Hello
+        "#};
+        assert_ok!(file.write_str(text));
+
+        let mut page = assert_ok!(Page::read(file.path().to_path_buf(), "+++"));
+        assert_eq!(
+            Some("bar"),
+            page.get_property("foo").and_then(YamlOwned::as_str)
+        );
+        page.insert_property("baz", "qux");
+        assert_ok!(page.write());
+        file.assert(indoc! {r#"
+            +++
+            baz = "qux"
+            foo = "bar"
+            +++
+            Hello
+        "#});
+
+        // Not properties with the default delimiter
+        let page = assert_ok!(Page::try_from(file.path()));
+        assert!(page.get_property("foo").is_none());
+
+        let mut page = assert_ok!(Page::read(temp_dir.child("new.md").to_path_buf(), "+++"));
+        page.insert_property("foo", "bar");
+        assert_ok!(page.write());
+        temp_dir.child("new.md").assert("+++\nfoo = \"bar\"\n+++\n");
+    }
+
+    #[test]
+    fn disable_properties() {
+        let temp_dir = assert_ok!(assert_fs::TempDir::new());
+        let file = temp_dir.child("page.md");
+        assert_ok!(file.write_str("---\nfoo: bar\n---\nHello\n"));
+
+        let mut page = assert_ok!(Page::try_from(file.path()));
+        page.disable_properties();
+        page.insert_property("foo", "baz");
+        page.insert_integer_property("week_number", 2);
+        page.insert_sequence_property("aliases", ["Monday"]);
+        assert!(!page.modified());
+
+        page.prepend_line("World");
+        assert_ok!(page.write());
+        file.assert("---\nfoo: bar\n---\nWorld\nHello\n");
+    }
+
+    #[test]
+    fn replace_marked_lines() {
+        let temp_dir = assert_ok!(assert_fs::TempDir::new());
+        let file = temp_dir.child("page.md");
+        assert_ok!(file.write_str("My notes\n"));
+
+        let mut page = assert_ok!(Page::try_from(file.path()));
+        page.replace_marked_lines("<start>", "<end>", Vec::<String>::new());
+        assert!(!page.modified());
+
+        page.replace_marked_lines("<start>", "<end>", ["Foo", "Bar"]);
+        assert!(page.modified());
+        assert_ok!(page.write());
+        file.assert("<start>\nFoo\nBar\n<end>\nMy notes\n");
+
+        let mut page = assert_ok!(Page::try_from(file.path()));
+        page.replace_marked_lines("<start>", "<end>", ["Foo", "Bar"]);
+        assert!(!page.modified());
+
+        page.replace_marked_lines("<start>", "<end>", ["Baz"]);
+        assert!(page.modified());
+        assert_ok!(page.write());
+        file.assert("<start>\nBaz\n<end>\nMy notes\n");
+
+        assert_ok!(file.write_str("Before\n<start>\nFoo\nAfter\n"));
+        let mut page = assert_ok!(Page::try_from(file.path()));
+        page.replace_marked_lines("<start>", "<end>", ["Baz"]);
+        assert_ok!(page.write());
+        file.assert("Before\n<start>\nBaz\n<end>\nFoo\nAfter\n");
+    }
+
+    #[test]
+    fn set_body() {
+        let temp_dir = assert_ok!(assert_fs::TempDir::new());
+        let file = temp_dir.child("page.md");
+        assert_ok!(file.write_str("---\nfoo: bar\n---\nHello\n"));
+
+        let mut page = assert_ok!(Page::try_from(file.path()));
+        page.set_body([Entry::Line("Hello".to_owned())]);
+        assert!(!page.modified());
+
+        page.set_body([
+            Entry::Line("World".to_owned()),
+            CodeBlock::toml("baz = 1\n").into(),
+        ]);
+        assert!(page.modified());
+        assert_ok!(page.write());
+        file.assert("---\nfoo: bar\n---\nWorld\n```toml\nbaz = 1\n```\n");
+
+        page.set_body([]);
+        assert!(page.modified());
+        assert_ok!(page.write());
+        file.assert("---\nfoo: bar\n---\n");
+    }
+
+    #[test]
+    fn restore_body() {
+        let temp_dir = assert_ok!(assert_fs::TempDir::new());
+        let file = temp_dir.child("page.md");
+        assert_ok!(file.write_str("Hello\nWorld\n"));
+
+        let original = assert_ok!(Page::try_from(file.path()));
+        let mut page = original.clone();
+        page.prepend_lines(["World", "Hello"]);
+        page.restore_body(&original);
+        assert!(!page.modified());
+
+        page.prepend_lines(["Foo", "Hello"]);
+        page.insert_property("foo", "bar");
+        page.restore_body(&original);
+        assert!(page.modified());
+        assert_ok!(page.write());
+        file.assert("---\nfoo: bar\n---\nHello\nWorld\nFoo\n");
+    }
+
+    #[test]
+    fn set_content() {
+        let temp_dir = assert_ok!(assert_fs::TempDir::new());
+        let file = temp_dir.child("page.md");
+
+        let mut page = assert_ok!(Page::read(file.to_path_buf(), "+++"));
+        assert_ok!(page.set_content("+++\nfoo = \"bar\"\n+++\n## Notes\n"));
+        assert!(page.modified());
+        assert_eq!(
+            Some("bar"),
+            page.get_property("foo").and_then(YamlOwned::as_str)
+        );
+
+        page.insert_property("baz", "qux");
+        page.prepend_line("Hello");
+        assert_ok!(page.write());
+        file.assert("+++\nbaz = \"qux\"\nfoo = \"bar\"\n+++\nHello\n## Notes\n");
+
+        assert_err!(page.set_content("+++\nfoo: bar\n+++\n"));
+    }
+
+    #[test]
+    fn insert_sequence_property() {
+        let temp_dir = assert_ok!(assert_fs::TempDir::new());
+        let file = temp_dir.child("page.md");
+        assert_ok!(file.write_str(indoc! {"
+            ---
+            aliases:
+              - Foo
+            ---
+        "}));
+        let mut page = assert_ok!(Page::try_from(file.path()));
+
+        page.insert_sequence_property("aliases", ["Foo"]);
+        assert!(!page.modified());
+
+        page.insert_sequence_property("aliases", ["Foo", "Bar"]);
+        assert!(page.modified());
+
+        assert_ok!(page.write());
+        file.assert(indoc! {"
+            ---
+            aliases:
+              - Foo
+              - Bar
+            ---
+        "});
+    }
+
     #[test]
     fn parse_page_from_path_and_write_it_again() {
         let temp_dir = assert_ok!(assert_fs::TempDir::new());
@@ -212,4 +730,34 @@ mod tests {
             ---
             {entries}"});
     }
+
+    #[test]
+    fn write_leaves_no_temporary_file() {
+        let temp_dir = assert_ok!(assert_fs::TempDir::new());
+        let file = temp_dir.child("page.md");
+        assert_ok!(file.write_str("Hello\n"));
+
+        let mut page = assert_ok!(Page::try_from(file.path()));
+        page.prepend_line("World");
+        assert_ok!(page.write());
+
+        file.assert("World\nHello\n");
+        assert!(!temp_dir.child(".page.md.tmp").path().exists());
+    }
+
+    #[test]
+    fn write_cleans_up_temporary_file_on_error() {
+        let temp_dir = assert_ok!(assert_fs::TempDir::new());
+        let file = temp_dir.child("page.md");
+        let mut page = assert_ok!(Page::try_from(file.path()));
+        page.prepend_line("Hello");
+
+        // A directory in place of the page makes the final rename fail
+        assert_ok!(file.create_dir_all());
+        assert_err!(page.write());
+
+        assert!(page.modified());
+        assert!(file.path().is_dir());
+        assert!(!temp_dir.child(".page.md.tmp").path().exists());
+    }
 }